@@ -0,0 +1,203 @@
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator, Result};
+use escrow::{Escrow, EscrowConfig, OfferBook, OfferBookHeader, OFFER_BOOK_PAGE_SIZE};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+// Escrow fields ahead of the mints are the discriminator, the seed and the maker
+pub const ESCROW_MINT_A_OFFSET: usize = 8 + 8 + 32;
+pub const ESCROW_MINT_B_OFFSET: usize = ESCROW_MINT_A_OFFSET + 32;
+
+pub fn is_escrow(data: &[u8]) -> bool {
+    data.starts_with(Escrow::DISCRIMINATOR)
+}
+
+pub fn decode_escrow(data: &[u8]) -> Result<Escrow> {
+    Escrow::try_deserialize(&mut &data[..])
+}
+
+// Auctions only store the price of their last fill or update, so listings
+// show what a take at `now` would actually be asked
+pub fn decode_offer(data: &[u8], now: i64) -> Result<Escrow> {
+    let mut offer = decode_escrow(data)?;
+    offer.refresh_receive(now);
+    Ok(offer)
+}
+
+pub fn decode_config(data: &[u8]) -> Result<EscrowConfig> {
+    EscrowConfig::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_book_header(data: &[u8]) -> Result<OfferBookHeader> {
+    OfferBookHeader::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_book(data: &[u8]) -> Result<OfferBook> {
+    OfferBook::try_deserialize(&mut &data[..])
+}
+
+// The first of the pair's pages with room for another offer, or the next
+// new page once every page is full. `books` are the pages in order
+pub fn open_book_page(header: Option<&OfferBookHeader>, books: &[OfferBook]) -> u32 {
+    let page_count = header.map_or(0, |header| header.page_count);
+
+    books
+        .iter()
+        .find(|book| book.entries.len() < OFFER_BOOK_PAGE_SIZE)
+        .map_or(page_count, |book| book.page)
+}
+
+// getProgramAccounts filters that only match escrows, and only those for
+// the given mints when set
+pub fn escrow_filters(mint_a: Option<&Pubkey>, mint_b: Option<&Pubkey>) -> Vec<RpcFilterType> {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+        0,
+        Escrow::DISCRIMINATOR.to_vec(),
+    ))];
+
+    if let Some(mint_a) = mint_a {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            ESCROW_MINT_A_OFFSET,
+            mint_a.to_bytes().to_vec(),
+        )));
+    }
+
+    if let Some(mint_b) = mint_b {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            ESCROW_MINT_B_OFFSET,
+            mint_b.to_bytes().to_vec(),
+        )));
+    }
+
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use escrow::{BookEntry, DutchAuction, TransferFeeMode};
+
+    fn sample_escrow() -> Escrow {
+        Escrow {
+            seed: 7,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            deposit: 100,
+            receive: 200,
+            expires_at: None,
+            allowed_taker: None,
+            auction: None,
+            fee_mode: TransferFeeMode::Gross,
+            book_page: 0,
+            fee_bps: 30,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn mint_offsets_match_the_escrow_layout() {
+        let escrow = sample_escrow();
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+
+        assert!(is_escrow(&data));
+        assert_eq!(
+            &data[ESCROW_MINT_A_OFFSET..ESCROW_MINT_A_OFFSET + 32],
+            escrow.mint_a.as_ref()
+        );
+        assert_eq!(
+            &data[ESCROW_MINT_B_OFFSET..ESCROW_MINT_B_OFFSET + 32],
+            escrow.mint_b.as_ref()
+        );
+        assert_eq!(decode_escrow(&data).unwrap().mint_b, escrow.mint_b);
+    }
+
+    #[test]
+    fn filters_only_match_the_requested_mints() {
+        let escrow = sample_escrow();
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+
+        let matches = |filters: Vec<RpcFilterType>| {
+            filters.iter().all(|filter| match filter {
+                RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&data),
+                _ => false,
+            })
+        };
+
+        assert!(matches(escrow_filters(None, None)));
+        assert!(matches(escrow_filters(Some(&escrow.mint_a), Some(&escrow.mint_b))));
+        assert!(!matches(escrow_filters(Some(&escrow.mint_b), None)));
+        assert!(!matches(escrow_filters(None, Some(&Pubkey::new_unique()))));
+    }
+
+    #[test]
+    fn offers_are_priced_at_the_given_time() {
+        let mut escrow = sample_escrow();
+        escrow.auction = Some(DutchAuction {
+            start_receive: 300,
+            floor_receive: 100,
+            start_at: 1_000,
+            end_at: 2_000,
+        });
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+
+        // The stored receive is left over from an earlier refresh
+        assert_eq!(decode_offer(&data, 1_250).unwrap().receive, 250);
+        assert_eq!(decode_offer(&data, 3_000).unwrap().receive, 100);
+
+        escrow.auction = None;
+        data.clear();
+        escrow.try_serialize(&mut data).unwrap();
+        assert_eq!(decode_offer(&data, 1_500).unwrap().receive, escrow.receive);
+    }
+
+    #[test]
+    fn open_book_page_is_the_first_with_room() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let book = |page: u32, len: usize| OfferBook {
+            mint_a,
+            mint_b,
+            page,
+            entries: (0..len)
+                .map(|_| BookEntry {
+                    escrow: Pubkey::new_unique(),
+                    deposit: 1,
+                    receive: 1,
+                })
+                .collect(),
+            bump: 255,
+        };
+        let header = |page_count: u32| OfferBookHeader {
+            mint_a,
+            mint_b,
+            page_count,
+            bump: 255,
+        };
+
+        assert_eq!(open_book_page(None, &[]), 0);
+        assert_eq!(open_book_page(Some(&header(1)), &[book(0, 3)]), 0);
+        assert_eq!(
+            open_book_page(
+                Some(&header(2)),
+                &[book(0, OFFER_BOOK_PAGE_SIZE), book(1, 5)]
+            ),
+            1
+        );
+        assert_eq!(
+            open_book_page(
+                Some(&header(2)),
+                &[book(0, OFFER_BOOK_PAGE_SIZE - 1), book(1, OFFER_BOOK_PAGE_SIZE)]
+            ),
+            0
+        );
+        assert_eq!(
+            open_book_page(
+                Some(&header(2)),
+                &[book(0, OFFER_BOOK_PAGE_SIZE), book(1, OFFER_BOOK_PAGE_SIZE)]
+            ),
+            2
+        );
+    }
+}
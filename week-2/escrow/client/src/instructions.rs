@@ -0,0 +1,213 @@
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program,
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token;
+use escrow::Escrow;
+
+use crate::{
+    pda::{
+        ata_address, book_address, book_header_address, config_address, escrow_address,
+        vault_address,
+    },
+    ESCROW_PROGRAM_ID,
+};
+
+// `token_program` is the program that owns both mints, legacy or Token-2022
+pub fn make(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    args: escrow::instruction::Make,
+) -> Instruction {
+    let escrow = escrow_address(maker, args.seed);
+
+    let accounts = escrow::accounts::Make {
+        maker: *maker,
+        config: config_address(),
+        mint_a: *mint_a,
+        mint_b: *mint_b,
+        maker_ata_a: ata_address(maker, mint_a, token_program),
+        escrow,
+        vault: vault_address(&escrow, mint_a, token_program),
+        book_header: book_header_address(mint_a, mint_b),
+        book: book_address(mint_a, mint_b, args.book_page),
+        associated_token_program: associated_token::ID,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+// `treasury` is read from the config account, the fee for the take is sent
+// to its associated token account for mint B
+pub fn take(
+    taker: &Pubkey,
+    escrow: &Escrow,
+    treasury: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let escrow_key = escrow_address(&escrow.maker, escrow.seed);
+
+    let accounts = escrow::accounts::Take {
+        taker: *taker,
+        maker: escrow.maker,
+        mint_a: escrow.mint_a,
+        mint_b: escrow.mint_b,
+        taker_ata_a: ata_address(taker, &escrow.mint_a, token_program),
+        taker_ata_b: ata_address(taker, &escrow.mint_b, token_program),
+        maker_ata_b: ata_address(&escrow.maker, &escrow.mint_b, token_program),
+        config: config_address(),
+        treasury: *treasury,
+        treasury_ata_b: ata_address(treasury, &escrow.mint_b, token_program),
+        escrow: escrow_key,
+        vault: vault_address(&escrow_key, &escrow.mint_a, token_program),
+        book: book_address(&escrow.mint_a, &escrow.mint_b, escrow.book_page),
+        associated_token_program: associated_token::ID,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::Take { amount }.data(),
+    }
+}
+
+pub fn refund(escrow: &Escrow, token_program: &Pubkey) -> Instruction {
+    let escrow_key = escrow_address(&escrow.maker, escrow.seed);
+
+    let accounts = escrow::accounts::Refund {
+        maker: escrow.maker,
+        mint_a: escrow.mint_a,
+        maker_ata_a: ata_address(&escrow.maker, &escrow.mint_a, token_program),
+        escrow: escrow_key,
+        vault: vault_address(&escrow_key, &escrow.mint_a, token_program),
+        book: book_address(&escrow.mint_a, &escrow.mint_b, escrow.book_page),
+        associated_token_program: associated_token::ID,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::Refund {}.data(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use anchor_spl::token;
+    use escrow::TransferFeeMode;
+
+    // (address, signer, writable) in the order the instruction lists them
+    fn metas(ix: &Instruction) -> Vec<(Pubkey, bool, bool)> {
+        ix.accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect()
+    }
+
+    #[test]
+    fn make_lists_accounts_in_program_order() {
+        let maker = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let escrow = escrow_address(&maker, 9);
+
+        let ix = make(
+            &maker,
+            &mint_a,
+            &mint_b,
+            &token::ID,
+            escrow::instruction::Make {
+                seed: 9,
+                deposit: 100,
+                receive: 200,
+                expires_at: None,
+                allowed_taker: None,
+                auction: None,
+                fee_mode: TransferFeeMode::Gross,
+                book_page: 3,
+            },
+        );
+
+        assert_eq!(ix.program_id, ESCROW_PROGRAM_ID);
+        assert!(ix.data.starts_with(escrow::instruction::Make::DISCRIMINATOR));
+        assert_eq!(
+            metas(&ix),
+            vec![
+                (maker, true, true),
+                (config_address(), false, false),
+                (mint_a, false, false),
+                (mint_b, false, false),
+                (ata_address(&maker, &mint_a, &token::ID), false, true),
+                (escrow, false, true),
+                (ata_address(&escrow, &mint_a, &token::ID), false, true),
+                (book_header_address(&mint_a, &mint_b), false, true),
+                (book_address(&mint_a, &mint_b, 3), false, true),
+                (associated_token::ID, false, false),
+                (token::ID, false, false),
+                (system_program::ID, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn take_lists_accounts_in_program_order() {
+        let taker = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let offer = Escrow {
+            seed: 9,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            deposit: 100,
+            receive: 200,
+            expires_at: None,
+            allowed_taker: None,
+            auction: None,
+            fee_mode: TransferFeeMode::Gross,
+            book_page: 2,
+            fee_bps: 30,
+            bump: 255,
+        };
+        let escrow = escrow_address(&offer.maker, offer.seed);
+
+        let ix = take(&taker, &offer, &treasury, &token::ID, 50);
+
+        assert_eq!(ix.data, escrow::instruction::Take { amount: 50 }.data());
+        assert_eq!(
+            metas(&ix),
+            vec![
+                (taker, true, true),
+                (offer.maker, false, true),
+                (offer.mint_a, false, true),
+                (offer.mint_b, false, false),
+                (ata_address(&taker, &offer.mint_a, &token::ID), false, true),
+                (ata_address(&taker, &offer.mint_b, &token::ID), false, true),
+                (ata_address(&offer.maker, &offer.mint_b, &token::ID), false, true),
+                (config_address(), false, false),
+                (treasury, false, false),
+                (ata_address(&treasury, &offer.mint_b, &token::ID), false, true),
+                (escrow, false, true),
+                (ata_address(&escrow, &offer.mint_a, &token::ID), false, true),
+                (book_address(&offer.mint_a, &offer.mint_b, 2), false, true),
+                (associated_token::ID, false, false),
+                (token::ID, false, false),
+                (system_program::ID, false, false),
+            ]
+        );
+    }
+}
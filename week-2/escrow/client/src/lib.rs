@@ -0,0 +1,8 @@
+// Helpers for calling the escrow program from Rust. Instructions are built
+// from the program's own generated account and argument types, so they
+// stay in step with the program
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use escrow::{self, ID as ESCROW_PROGRAM_ID};
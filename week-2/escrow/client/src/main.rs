@@ -0,0 +1,309 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use escrow_client::{
+    accounts::{
+        decode_book, decode_book_header, decode_config, decode_escrow, decode_offer,
+        escrow_filters, open_book_page,
+    },
+    escrow::{instruction, Escrow, OfferBook, OfferBookHeader, TransferFeeMode},
+    instructions, pda, ESCROW_PROGRAM_ID,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+
+#[derive(Parser)]
+#[command(about = "List, make, take and refund escrow offers")]
+struct Cli {
+    /// RPC endpoint, a local test validator by default
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair that signs and pays, the Solana CLI wallet by default
+    #[arg(long)]
+    keypair: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List open offers, read from the offer book and sorted by price when
+    /// both mints are given
+    List {
+        #[arg(long)]
+        mint_a: Option<Pubkey>,
+        #[arg(long)]
+        mint_b: Option<Pubkey>,
+    },
+    /// Deposit mint A and ask for mint B in return
+    Make {
+        #[arg(long)]
+        mint_a: Pubkey,
+        #[arg(long)]
+        mint_b: Pubkey,
+        #[arg(long)]
+        seed: u64,
+        #[arg(long)]
+        deposit: u64,
+        #[arg(long)]
+        receive: u64,
+        /// Unix timestamp the offer can no longer be taken from
+        #[arg(long)]
+        expires_at: Option<i64>,
+        /// Only this taker can fill the offer
+        #[arg(long)]
+        taker: Option<Pubkey>,
+        /// Treat amounts as net of Token-2022 transfer fees
+        #[arg(long)]
+        net: bool,
+        /// Offer book page to list on, the first one with room by default
+        #[arg(long)]
+        book_page: Option<u32>,
+    },
+    /// Fill an offer, all of what is left unless an amount is given
+    Take {
+        escrow: Pubkey,
+        #[arg(long)]
+        amount: Option<u64>,
+    },
+    /// Close one of your offers and get the deposit back
+    Refund {
+        escrow: Pubkey,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let client = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME")?).join(".config/solana/id.json"),
+    };
+
+    match cli.command {
+        Command::List { mint_a, mint_b } => list(&client, mint_a, mint_b),
+        Command::Make {
+            mint_a,
+            mint_b,
+            seed,
+            deposit,
+            receive,
+            expires_at,
+            taker,
+            net,
+            book_page,
+        } => {
+            let payer = load_keypair(&keypair_path)?;
+            let token_program = token_program(&client, &mint_a)?;
+            let book_page = match book_page {
+                Some(book_page) => book_page,
+                None => book_page_with_room(&client, &mint_a, &mint_b)?,
+            };
+
+            let args = instruction::Make {
+                seed,
+                deposit,
+                receive,
+                expires_at,
+                allowed_taker: taker,
+                auction: None,
+                fee_mode: if net {
+                    TransferFeeMode::Net
+                } else {
+                    TransferFeeMode::Gross
+                },
+                book_page,
+            };
+
+            let ix = instructions::make(&payer.pubkey(), &mint_a, &mint_b, &token_program, args);
+            let signature = send(&client, &payer, ix)?;
+
+            println!("Made {}", pda::escrow_address(&payer.pubkey(), seed));
+            println!("Signature {signature}");
+            Ok(())
+        }
+        Command::Take { escrow, amount } => {
+            let payer = load_keypair(&keypair_path)?;
+            let offer = fetch_escrow(&client, &escrow)?;
+            let token_program = token_program(&client, &offer.mint_a)?;
+            let config = decode_config(&client.get_account(&pda::config_address())?.data)?;
+
+            let ix = instructions::take(
+                &payer.pubkey(),
+                &offer,
+                &config.treasury,
+                &token_program,
+                amount.unwrap_or(offer.deposit),
+            );
+            let signature = send(&client, &payer, ix)?;
+
+            println!("Signature {signature}");
+            Ok(())
+        }
+        Command::Refund { escrow } => {
+            let payer = load_keypair(&keypair_path)?;
+            let offer = fetch_escrow(&client, &escrow)?;
+
+            if offer.maker != payer.pubkey() {
+                bail!("{escrow} was made by {}", offer.maker);
+            }
+
+            let token_program = token_program(&client, &offer.mint_a)?;
+            let signature = send(&client, &payer, instructions::refund(&offer, &token_program))?;
+
+            println!("Signature {signature}");
+            Ok(())
+        }
+    }
+}
+
+fn list(client: &RpcClient, mint_a: Option<Pubkey>, mint_b: Option<Pubkey>) -> Result<()> {
+    // Auction prices are worked out at the cluster's time, not the local clock
+    let now = client.get_block_time(client.get_slot()?)?;
+
+    let offers = match (mint_a, mint_b) {
+        (Some(mint_a), Some(mint_b)) => book_offers(client, &mint_a, &mint_b, now)?,
+        _ => scan_offers(client, mint_a.as_ref(), mint_b.as_ref(), now)?,
+    };
+
+    for (address, offer) in offers {
+        println!(
+            "{address} maker {} offers {} of {} for {} of {}{}",
+            offer.maker,
+            offer.deposit,
+            offer.mint_a,
+            offer.receive,
+            offer.mint_b,
+            offer
+                .expires_at
+                .map(|expires_at| format!(", expires at {expires_at}"))
+                .unwrap_or_default(),
+        );
+    }
+
+    Ok(())
+}
+
+fn book_header(client: &RpcClient, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<Option<OfferBookHeader>> {
+    let header_address = pda::book_header_address(mint_a, mint_b);
+
+    client
+        .get_account_with_commitment(&header_address, client.commitment())?
+        .value
+        .map(|header| Ok(decode_book_header(&header.data)?))
+        .transpose()
+}
+
+fn book_pages(client: &RpcClient, mint_a: &Pubkey, mint_b: &Pubkey, page_count: u32) -> Result<Vec<OfferBook>> {
+    (0..page_count)
+        .map(|page| {
+            let book = client.get_account(&pda::book_address(mint_a, mint_b, page))?;
+            Ok(decode_book(&book.data)?)
+        })
+        .collect()
+}
+
+fn book_page_with_room(client: &RpcClient, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<u32> {
+    let header = book_header(client, mint_a, mint_b)?;
+    let page_count = header.as_ref().map_or(0, |header| header.page_count);
+    let books = book_pages(client, mint_a, mint_b, page_count)?;
+
+    Ok(open_book_page(header.as_ref(), &books))
+}
+
+// Every page of the pair's book, cheapest mint B per mint A first
+fn book_offers(
+    client: &RpcClient,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    now: i64,
+) -> Result<Vec<(Pubkey, Escrow)>> {
+    let Some(header) = book_header(client, mint_a, mint_b)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut offers = Vec::new();
+
+    for book in book_pages(client, mint_a, mint_b, header.page_count)? {
+        let addresses: Vec<Pubkey> = book.entries.iter().map(|entry| entry.escrow).collect();
+
+        let accounts = client.get_multiple_accounts(&addresses)?;
+
+        for (address, account) in addresses.into_iter().zip(accounts) {
+            if let Some(account) = account {
+                offers.push((address, decode_offer(&account.data, now)?));
+            }
+        }
+    }
+
+    // receive / deposit compared without dividing
+    offers.sort_by(|(_, a), (_, b)| {
+        (u128::from(a.receive) * u128::from(b.deposit))
+            .cmp(&(u128::from(b.receive) * u128::from(a.deposit)))
+    });
+
+    Ok(offers)
+}
+
+fn scan_offers(
+    client: &RpcClient,
+    mint_a: Option<&Pubkey>,
+    mint_b: Option<&Pubkey>,
+    now: i64,
+) -> Result<Vec<(Pubkey, Escrow)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(escrow_filters(mint_a, mint_b)),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    client
+        .get_program_accounts_with_config(&ESCROW_PROGRAM_ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode_offer(&account.data, now)?)))
+        .collect()
+}
+
+fn load_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path)
+        .map_err(|err| anyhow::anyhow!("couldn't read keypair {}: {err}", path.display()))
+}
+
+fn fetch_escrow(client: &RpcClient, address: &Pubkey) -> Result<Escrow> {
+    let account = client
+        .get_account(address)
+        .with_context(|| format!("no escrow at {address}"))?;
+
+    Ok(decode_escrow(&account.data)?)
+}
+
+// Both mints of an offer live under the same token program
+fn token_program(client: &RpcClient, mint: &Pubkey) -> Result<Pubkey> {
+    Ok(client.get_account(mint)?.owner)
+}
+
+fn send(client: &RpcClient, payer: &Keypair, ix: Instruction) -> Result<Signature> {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        client.get_latest_blockhash()?,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::ESCROW_PROGRAM_ID;
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &ESCROW_PROGRAM_ID).0
+}
+
+pub fn escrow_address(maker: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"escrow", maker.as_ref(), &seed.to_le_bytes()],
+        &ESCROW_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn book_header_address(mint_a: &Pubkey, mint_b: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"book_header", mint_a.as_ref(), mint_b.as_ref()],
+        &ESCROW_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn book_address(mint_a: &Pubkey, mint_b: &Pubkey, page: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"book", mint_a.as_ref(), mint_b.as_ref(), &page.to_le_bytes()],
+        &ESCROW_PROGRAM_ID,
+    )
+    .0
+}
+
+// The vault is the escrow's associated token account for mint A
+pub fn vault_address(escrow: &Pubkey, mint_a: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    ata_address(escrow, mint_a, token_program)
+}
+
+pub fn ata_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::{associated_token, token, token_2022};
+
+    #[test]
+    fn vault_is_the_escrows_associated_token_account() {
+        let escrow = escrow_address(&Pubkey::new_unique(), 42);
+        let mint = Pubkey::new_unique();
+
+        // Seeds of the associated token program, derived by hand
+        let expected = Pubkey::find_program_address(
+            &[escrow.as_ref(), token::ID.as_ref(), mint.as_ref()],
+            &associated_token::ID,
+        )
+        .0;
+
+        assert_eq!(vault_address(&escrow, &mint, &token::ID), expected);
+        assert_ne!(
+            vault_address(&escrow, &mint, &token_2022::ID),
+            vault_address(&escrow, &mint, &token::ID)
+        );
+    }
+
+    #[test]
+    fn escrow_seed_is_little_endian() {
+        let maker = Pubkey::new_unique();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), &[1, 0, 0, 0, 0, 0, 0, 0]],
+            &ESCROW_PROGRAM_ID,
+        );
+
+        assert_eq!(escrow_address(&maker, 1), expected);
+        assert!(Pubkey::create_program_address(
+            &[b"escrow", maker.as_ref(), &1u64.to_le_bytes(), &[bump]],
+            &ESCROW_PROGRAM_ID
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn book_pages_share_a_header_per_pair() {
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+
+        assert_ne!(book_address(&mint_a, &mint_b, 0), book_address(&mint_a, &mint_b, 1));
+        assert_ne!(book_address(&mint_a, &mint_b, 0), book_address(&mint_b, &mint_a, 0));
+        assert_eq!(
+            book_address(&mint_a, &mint_b, 1),
+            Pubkey::find_program_address(
+                &[b"book", mint_a.as_ref(), mint_b.as_ref(), &[1, 0, 0, 0]],
+                &ESCROW_PROGRAM_ID
+            )
+            .0
+        );
+        assert_eq!(
+            book_header_address(&mint_a, &mint_b),
+            Pubkey::find_program_address(
+                &[b"book_header", mint_a.as_ref(), mint_b.as_ref()],
+                &ESCROW_PROGRAM_ID
+            )
+            .0
+        );
+    }
+}
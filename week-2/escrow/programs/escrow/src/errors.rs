@@ -0,0 +1,71 @@
+use anchor_lang::error_code;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Overflow")]
+    Overflow,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Offer expired")]
+    OfferExpired,
+    #[msg("Offer has not expired")]
+    OfferNotExpired,
+    #[msg("Offer is reserved for a different taker")]
+    TakerNotAllowed,
+    #[msg("Fee is greater than 100%")]
+    InvalidFee,
+    #[msg("Making new offers is paused")]
+    Paused,
+    #[msg("Basket must have between one and four legs on each side")]
+    InvalidBasket,
+    #[msg("Basket lists the same mint twice")]
+    DuplicateMint,
+    #[msg("Wrong number of remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Mint does not match the offer")]
+    InvalidMint,
+    #[msg("Token account is not the expected associated token account")]
+    InvalidTokenAccount,
+    #[msg("Asset or collection does not match the offer")]
+    InvalidAsset,
+    #[msg("Auction floor must be above zero and below the start price, and it must end after it starts")]
+    InvalidAuction,
+    #[msg("Auction offers are priced by the auction")]
+    AuctionPriced,
+    #[msg("Timeout must be greater than zero")]
+    InvalidTimeout,
+    #[msg("Escrow is not in the right state for this")]
+    InvalidStatus,
+    #[msg("Signer is not a party to the escrow")]
+    NotAParty,
+    #[msg("Signer is not the arbiter")]
+    NotArbiter,
+    #[msg("The arbiter can't be the maker or the taker")]
+    InvalidArbiter,
+    #[msg("Escrow can't be released yet")]
+    NotReleasable,
+    #[msg("Must have between one and four milestones, none of them released")]
+    InvalidMilestones,
+    #[msg("Milestone index out of range")]
+    InvalidMilestone,
+    #[msg("Milestone already released")]
+    MilestoneReleased,
+    #[msg("A milestone is ready to be released")]
+    MilestonePending,
+    #[msg("Offer book page is full")]
+    BookPageFull,
+    #[msg("Offer book page has to be an open page or the next new one")]
+    InvalidBookPage,
+    #[msg("Offer is not listed on this offer book page")]
+    NotInBook,
+    #[msg("Offer has changed since the counter-offer was made")]
+    CounterOfferStale,
+    #[msg("Only the program's upgrade authority can do this")]
+    NotUpgradeAuthority,
+    #[msg("Refunding timestamp-locked milestones needs the payee's signature")]
+    PayeeSignatureRequired,
+    #[msg("Offered and requested mints must differ")]
+    SameMint,
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OfferUpdatedEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub deposit: u64,
+    pub receive: u64,
+    pub expires_at: Option<i64>,
+}
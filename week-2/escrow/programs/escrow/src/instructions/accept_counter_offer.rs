@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{protocol_fee, CounterOffer, Escrow, EscrowConfig, OfferBook},
+    utils::{check_ata, close_token_account, create_ata, transfer_tokens},
+};
+
+// Settles the whole escrow at the counter-offer's price: the counter vault
+// goes to the maker less the protocol fee, the escrow vault to the taker
+#[derive(Accounts)]
+pub struct AcceptCounterOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub taker: SystemAccount<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, EscrowConfig>>,
+    #[account(
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    // Checked and created only when a fee is charged
+    #[account(
+        mut
+    )]
+    /// CHECK: The treasury's associated token account for mint B
+    pub treasury_ata_b: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"book",
+            escrow.mint_a.as_ref(),
+            escrow.mint_b.as_ref(),
+            escrow.book_page.to_le_bytes().as_ref()
+        ],
+        bump = book.bump
+    )]
+    pub book: Box<Account<'info, OfferBook>>,
+    #[account(
+        mut,
+        close = taker,
+        has_one = escrow,
+        has_one = taker,
+        has_one = mint_a @ EscrowError::CounterOfferStale,
+        has_one = mint_b @ EscrowError::CounterOfferStale,
+        seeds = [b"counter", escrow.key().as_ref(), taker.key().as_ref()],
+        bump = counter_offer.bump
+    )]
+    pub counter_offer: Box<Account<'info, CounterOffer>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = counter_offer,
+        associated_token::token_program = token_program
+    )]
+    pub counter_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptCounterOffer<'info> {
+    pub fn accept_counter_offer(&mut self) -> Result<()> {
+        require!(
+            !self.escrow.is_expired(Clock::get()?.unix_timestamp),
+            EscrowError::OfferExpired
+        );
+        // The taker only agreed to their price for what was left back then
+        require_eq!(
+            self.counter_offer.deposit,
+            self.escrow.deposit,
+            EscrowError::CounterOfferStale
+        );
+
+        self.pay_maker()?;
+        self.withdraw_and_close_vault()?;
+
+        self.book.remove(&self.escrow.key())
+    }
+
+    fn pay_maker(&self) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"counter",
+            self.counter_offer.escrow.as_ref(),
+            self.taker.to_account_info().key.as_ref(),
+            &[self.counter_offer.bump],
+        ]];
+
+        // The protocol fee comes out of what the maker receives
+        let fee = protocol_fee(self.counter_vault.amount, self.escrow.fee_bps)?;
+
+        if fee > 0 {
+            check_ata(
+                &self.treasury_ata_b,
+                &self.treasury.key(),
+                &self.mint_b.key(),
+                &self.token_program.key(),
+            )?;
+
+            create_ata(
+                self.maker.to_account_info(),
+                self.treasury_ata_b.to_account_info(),
+                self.treasury.to_account_info(),
+                self.mint_b.to_account_info(),
+                self.system_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.associated_token_program.to_account_info(),
+                true,
+            )?;
+
+            transfer_tokens(
+                self.counter_vault.to_account_info(),
+                self.treasury_ata_b.to_account_info(),
+                self.counter_offer.to_account_info(),
+                &self.mint_b,
+                self.token_program.to_account_info(),
+                fee,
+                signer_seeds,
+            )?;
+        }
+
+        transfer_tokens(
+            self.counter_vault.to_account_info(),
+            self.maker_ata_b.to_account_info(),
+            self.counter_offer.to_account_info(),
+            &self.mint_b,
+            self.token_program.to_account_info(),
+            self.counter_vault.amount - fee,
+            signer_seeds,
+        )?;
+
+        close_token_account(
+            self.counter_vault.to_account_info(),
+            self.mint_b.to_account_info(),
+            self.taker.to_account_info(),
+            self.counter_offer.to_account_info(),
+            self.token_program.to_account_info(),
+            signer_seeds,
+        )
+    }
+
+    fn withdraw_and_close_vault(&self) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        transfer_tokens(
+            self.vault.to_account_info(),
+            self.taker_ata_a.to_account_info(),
+            self.escrow.to_account_info(),
+            &self.mint_a,
+            self.token_program.to_account_info(),
+            self.vault.amount,
+            signer_seeds,
+        )?;
+
+        close_token_account(
+            self.vault.to_account_info(),
+            self.mint_a.to_account_info(),
+            self.maker.to_account_info(),
+            self.escrow.to_account_info(),
+            self.token_program.to_account_info(),
+            signer_seeds,
+        )
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, state::MilestoneEscrow};
+
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [b"milestones", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, MilestoneEscrow>,
+}
+
+impl<'info> ApproveMilestone<'info> {
+    pub fn approve_milestone(&mut self, index: u8) -> Result<()> {
+        require!(index < self.escrow.milestone_count, EscrowError::InvalidMilestone);
+
+        let milestone = &mut self.escrow.milestones[usize::from(index)];
+
+        require!(!milestone.released, EscrowError::MilestoneReleased);
+
+        milestone.approved = true;
+        Ok(())
+    }
+}
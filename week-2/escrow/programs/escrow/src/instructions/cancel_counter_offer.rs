@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, 
+        Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::CounterOffer,
+    utils::harvest_withheld_fees,
+};
+
+// Shared by reject_counter_offer and withdraw_counter_offer, either way the
+// mint B goes back to the taker. Doesn't need the escrow, so it still works
+// once the escrow has been filled or refunded
+#[derive(Accounts)]
+pub struct CancelCounterOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub signer: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub taker: SystemAccount<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = taker,
+        has_one = taker,
+        has_one = mint_b,
+        seeds = [b"counter", counter_offer.escrow.as_ref(), taker.key().as_ref()],
+        bump = counter_offer.bump
+    )]
+    pub counter_offer: Account<'info, CounterOffer>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = counter_offer,
+        associated_token::token_program = token_program
+    )]
+    pub counter_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelCounterOffer<'info> {
+    pub fn reject_counter_offer(&mut self) -> Result<()> {
+        require_keys_eq!(self.signer.key(), self.counter_offer.maker, EscrowError::NotAParty);
+
+        self.refund_and_close_vault()
+    }
+
+    pub fn withdraw_counter_offer(&mut self) -> Result<()> {
+        require_keys_eq!(self.signer.key(), self.counter_offer.taker, EscrowError::NotAParty);
+
+        self.refund_and_close_vault()
+    }
+
+    fn refund_and_close_vault(&mut self) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"counter",
+            self.counter_offer.escrow.as_ref(),
+            self.taker.to_account_info().key.as_ref(),
+            &[self.counter_offer.bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.counter_vault.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.taker_ata_b.to_account_info(),
+            authority: self.counter_offer.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.counter_vault.amount, self.mint_b.decimals)?;
+
+        harvest_withheld_fees(
+            self.counter_vault.to_account_info(),
+            self.mint_b.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
+
+        let close_accounts = CloseAccount {
+            account: self.counter_vault.to_account_info(),
+            destination: self.taker.to_account_info(),
+            authority: self.counter_offer.to_account_info(),
+        };
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, 
+        Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, OfferBook},
+    utils::harvest_withheld_fees,
+};
+
+// Permissionless, anyone can clean up an expired offer and the 
+// tokens and rent go back to the maker
+#[derive(Accounts)]
+pub struct Expire<'info> {
+    #[account(
+        mut
+    )]
+    pub caller: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint_a,
+        has_one = maker,
+        seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"book",
+            escrow.mint_a.as_ref(),
+            escrow.mint_b.as_ref(),
+            escrow.book_page.to_le_bytes().as_ref()
+        ],
+        bump = book.bump
+    )]
+    pub book: Account<'info, OfferBook>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Expire<'info> {
+    pub fn expire_and_close_vault(&mut self) -> Result<()> {
+        require!(
+            self.escrow.is_expired(Clock::get()?.unix_timestamp),
+            EscrowError::OfferNotExpired
+        );
+
+        self.book.remove(&self.escrow.key())?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.maker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let tranfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(tranfer_ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        harvest_withheld_fees(
+            self.vault.to_account_info(),
+            self.mint_a.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
+
+        let close_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        transfer_checked, Mint, TokenAccount, 
+        TokenInterface, TransferChecked
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{ArbitratedEscrow, ArbitratedStatus},
+};
+
+// The taker locks mint B in a second vault instead of paying the maker,
+// which starts the release timeout
+#[derive(Accounts)]
+pub struct FundArbitrated<'info> {
+    #[account(
+        mut
+    )]
+    pub taker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint_b,
+        constraint = escrow.status == ArbitratedStatus::Open @ EscrowError::InvalidStatus,
+        seeds = [b"arbitrated", escrow.maker.as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, ArbitratedEscrow>,
+    // Anyone can create the vault ahead of time, which mustn't block funding
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundArbitrated<'info> {
+    pub fn fund_arbitrated(&mut self) -> Result<()> {
+        require_keys_neq!(self.taker.key(), self.escrow.arbiter, EscrowError::InvalidArbiter);
+
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.vault_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(transfer_ctx, self.escrow.receive, self.mint_b.decimals)?;
+
+        self.escrow.taker = Some(self.taker.key());
+        self.escrow.release_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(self.escrow.timeout)
+            .ok_or(EscrowError::Overflow)?;
+        self.escrow.status = ArbitratedStatus::Funded;
+        Ok(())
+    }
+}
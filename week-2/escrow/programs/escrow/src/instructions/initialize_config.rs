@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::EscrowError,
+    program::Escrow,
+    state::{EscrowConfig, MAX_FEE_BPS},
+};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        mut
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"config"],
+        space = EscrowConfig::DISCRIMINATOR.len() + EscrowConfig::INIT_SPACE,
+        bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    // Only the upgrade authority can claim the admin role, so nobody can front-run the deploy
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+    )]
+    pub program: Program<'info, Escrow>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) 
+            @ EscrowError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(
+        &mut self, 
+        fee_bps: u16, 
+        treasury: Pubkey, 
+        bumps: &InitializeConfigBumps
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, EscrowError::InvalidFee);
+
+        self.config.set_inner(EscrowConfig {
+            admin: self.admin.key(),
+            fee_bps,
+            treasury,
+            paused: false,
+            bump: bumps.config,
+        });
+        Ok(())
+    }
+}
@@ -8,15 +8,34 @@ use anchor_spl::{
     },
 };
 
-use crate::state::Escrow;
+use crate::{
+    errors::EscrowError,
+    state::{DutchAuction, Escrow, EscrowConfig, OfferBook, OfferBookHeader, TransferFeeMode},
+    utils::amount_to_send,
+};
 
 #[derive(Accounts)]
-#[instruction(seed: u64)]
+#[instruction(
+    seed: u64,
+    deposit: u64,
+    receive: u64,
+    expires_at: Option<i64>,
+    allowed_taker: Option<Pubkey>,
+    auction: Option<DutchAuction>,
+    fee_mode: TransferFeeMode,
+    book_page: u32
+)]
 pub struct Make<'info> {
     #[account(
         mut
     )]
     pub maker: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::Paused
+    )]
+    pub config: Account<'info, EscrowConfig>,
     #[account(
         mint::token_program = token_program
     )]
@@ -48,6 +67,27 @@ pub struct Make<'info> {
         associated_token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        seeds = [b"book_header", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        space = OfferBookHeader::DISCRIMINATOR.len() + OfferBookHeader::INIT_SPACE,
+        bump
+    )]
+    pub book_header: Box<Account<'info, OfferBookHeader>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        seeds = [
+            b"book",
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            book_page.to_le_bytes().as_ref()
+        ],
+        space = OfferBook::DISCRIMINATOR.len() + OfferBook::INIT_SPACE,
+        bump
+    )]
+    pub book: Box<Account<'info, OfferBook>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -55,19 +95,54 @@ pub struct Make<'info> {
 }
 
 impl<'info> Make<'info> {
-    pub fn init_escrow(&mut self, seed: u64, receive: u64, bumps: &MakeBumps) -> Result<()> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn init_escrow(
+        &mut self, 
+        seed: u64, 
+        receive: u64, 
+        expires_at: Option<i64>, 
+        allowed_taker: Option<Pubkey>, 
+        auction: Option<DutchAuction>,
+        fee_mode: TransferFeeMode,
+        book_page: u32,
+        bumps: &MakeBumps
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        if let Some(expires_at) = expires_at {
+            require!(expires_at > now, EscrowError::InvalidExpiry);
+        }
+
+        if let Some(auction) = &auction {
+            auction.validate()?;
+        }
+
         self.escrow.set_inner(Escrow {
             seed,
             maker: self.maker.key(),
             mint_a: self.mint_a.key(),
             mint_b: self.mint_b.key(),
+            deposit: self.vault.amount,
             receive,
+            expires_at,
+            allowed_taker,
+            auction,
+            fee_mode,
+            book_page,
+            fee_bps: self.config.fee_bps,
             bump: bumps.escrow,
         });
+
+        // `receive` follows the auction price from here on
+        self.escrow.refresh_receive(now);
         Ok(())
     }
 
-    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+    // The escrow records the vault balance afterwards, which is less than
+    // `deposit` for gross deposits of mints with a transfer fee
+    pub fn deposit(&mut self, deposit: u64, fee_mode: TransferFeeMode) -> Result<()> {
+        let amount = amount_to_send(&self.mint_a.to_account_info(), deposit, fee_mode)?;
+
         let tranfer_accounts = TransferChecked {
             from: self.maker_ata_a.to_account_info(),
             mint: self.mint_a.to_account_info(),
@@ -77,6 +152,35 @@ impl<'info> Make<'info> {
 
         let deposit_ctx = CpiContext::new(self.token_program.to_account_info(), tranfer_accounts);
 
-        transfer_checked(deposit_ctx, deposit, self.mint_a.decimals)
+        transfer_checked(deposit_ctx, amount, self.mint_a.decimals)?;
+
+        self.vault.reload()
+    }
+
+    pub fn list_offer(&mut self, book_page: u32, bumps: &MakeBumps) -> Result<()> {
+        // First offer for the mint pair
+        if self.book_header.mint_a == Pubkey::default() {
+            self.book_header.mint_a = self.mint_a.key();
+            self.book_header.mint_b = self.mint_b.key();
+            self.book_header.bump = bumps.book_header;
+        }
+
+        // Pages are opened in order, so every page below page_count exists
+        require!(
+            book_page <= self.book_header.page_count,
+            EscrowError::InvalidBookPage
+        );
+
+        // First offer on a new page
+        if book_page == self.book_header.page_count {
+            self.book_header.page_count += 1;
+            self.book.mint_a = self.mint_a.key();
+            self.book.mint_b = self.mint_b.key();
+            self.book.page = book_page;
+            self.book.bump = bumps.book;
+        }
+
+        self.book
+            .insert(self.escrow.key(), self.escrow.deposit, self.escrow.receive)
     }
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        transfer_checked, Mint, TokenAccount, 
+        TokenInterface, TransferChecked
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{ArbitratedEscrow, ArbitratedStatus, EscrowConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeArbitrated<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::Paused
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"arbitrated", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = ArbitratedEscrow::DISCRIMINATOR.len() + ArbitratedEscrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, ArbitratedEscrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeArbitrated<'info> {
+    pub fn make_arbitrated(
+        &mut self,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+        arbiter: Pubkey,
+        timeout: i64,
+        bumps: &MakeArbitratedBumps,
+    ) -> Result<()> {
+        require!(deposit > 0 && receive > 0, EscrowError::InvalidAmount);
+        require!(timeout > 0, EscrowError::InvalidTimeout);
+        require_keys_neq!(arbiter, self.maker.key(), EscrowError::InvalidArbiter);
+        // Both vaults would be the same account
+        require_keys_neq!(self.mint_a.key(), self.mint_b.key(), EscrowError::SameMint);
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault_a.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(transfer_ctx, deposit, self.mint_a.decimals)?;
+
+        self.escrow.set_inner(ArbitratedEscrow {
+            seed,
+            maker: self.maker.key(),
+            taker: None,
+            arbiter,
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            deposit,
+            receive,
+            timeout,
+            release_at: 0,
+            status: ArbitratedStatus::Open,
+            maker_confirmed: false,
+            taker_confirmed: false,
+            fee_bps: self.config.fee_bps,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+}
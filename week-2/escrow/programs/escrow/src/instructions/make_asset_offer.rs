@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+};
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    errors::EscrowError,
+    state::{AssetEscrow, EscrowConfig, OfferLeg},
+    utils::{
+        check_asset, check_ata, create_ata, load_mint, token_program_for, transfer_asset,
+        transfer_tokens,
+    },
+};
+
+pub const MAKE_ASSET_OFFER_TOKEN_ACCOUNTS: usize = 3;
+pub const ASSET_LEG_ACCOUNTS: usize = 2;
+
+// Remaining accounts, for the offered leg:
+// token: [mint, maker's token account, vault]
+// asset: [asset, collection or the core program when there is none]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeAssetOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::Paused
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"asset_escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = AssetEscrow::DISCRIMINATOR.len() + AssetEscrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, AssetEscrow>,
+
+    #[account(
+        address = CORE_PROGRAM_ID
+    )]
+    /// CHECK: This is the MPL_CORE_PROGRAM
+    pub core_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeAssetOffer<'info> {
+    pub fn make_asset_offer(
+        &mut self,
+        seed: u64,
+        offered: OfferLeg,
+        requested: OfferLeg,
+        bumps: &MakeAssetOfferBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        check_leg(&offered)?;
+        check_leg(&requested)?;
+
+        match &offered {
+            OfferLeg::Token { mint, amount } => {
+                require_eq!(
+                    remaining_accounts.len(),
+                    MAKE_ASSET_OFFER_TOKEN_ACCOUNTS,
+                    EscrowError::InvalidRemainingAccounts
+                );
+
+                let token_program = token_program_for(
+                    &remaining_accounts[0],
+                    &self.token_program,
+                    &self.token_2022_program,
+                )?;
+                let mint = load_mint(&remaining_accounts[0], mint, token_program.key)?;
+                let maker_ata = &remaining_accounts[1];
+                let vault = &remaining_accounts[2];
+
+                check_ata(vault, &self.escrow.key(), &mint.key(), token_program.key)?;
+
+                create_ata(
+                    self.maker.to_account_info(),
+                    vault.clone(),
+                    self.escrow.to_account_info(),
+                    mint.to_account_info(),
+                    self.system_program.to_account_info(),
+                    token_program.clone(),
+                    self.associated_token_program.to_account_info(),
+                    false,
+                )?;
+
+                transfer_tokens(
+                    maker_ata.clone(),
+                    vault.clone(),
+                    self.maker.to_account_info(),
+                    &mint,
+                    token_program.clone(),
+                    *amount,
+                    &[],
+                )?;
+            }
+            OfferLeg::Asset { asset, collection } => {
+                require_eq!(
+                    remaining_accounts.len(),
+                    ASSET_LEG_ACCOUNTS,
+                    EscrowError::InvalidRemainingAccounts
+                );
+
+                let asset_info = &remaining_accounts[0];
+                let collection_info = &remaining_accounts[1];
+
+                check_asset(asset_info, collection_info, asset, collection)?;
+
+                // The escrow becomes the owner of the asset until the offer is
+                // taken or refunded
+                transfer_asset(
+                    asset_info,
+                    collection.is_some().then_some(collection_info),
+                    &self.maker.to_account_info(),
+                    &self.maker.to_account_info(),
+                    &self.escrow.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    &self.core_program.to_account_info(),
+                    &[],
+                )?;
+            }
+        }
+
+        self.escrow.set_inner(AssetEscrow {
+            seed,
+            maker: self.maker.key(),
+            offered,
+            requested,
+            fee_bps: self.config.fee_bps,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+}
+
+fn check_leg(leg: &OfferLeg) -> Result<()> {
+    if let OfferLeg::Token { amount, .. } = leg {
+        require!(*amount > 0, EscrowError::InvalidAmount);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{BasketEscrow, BasketLeg, EscrowConfig, MAX_BASKET_LEGS},
+    utils::{check_ata, create_ata, load_mint, token_program_for, transfer_tokens},
+};
+
+pub const MAKE_BASKET_ACCOUNTS_PER_LEG: usize = 3;
+
+// Remaining accounts, for every offered leg in order: 
+// [mint, maker's token account, vault]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeBasket<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::Paused
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"basket", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = BasketEscrow::DISCRIMINATOR.len() + BasketEscrow::INIT_SPACE,
+        bump
+    )]
+    pub basket: Account<'info, BasketEscrow>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeBasket<'info> {
+    pub fn make_basket(
+        &mut self,
+        seed: u64,
+        offered: Vec<BasketLeg>,
+        requested: Vec<BasketLeg>,
+        bumps: &MakeBasketBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        check_legs(&offered)?;
+        check_legs(&requested)?;
+
+        require_eq!(
+            remaining_accounts.len(),
+            offered.len() * MAKE_BASKET_ACCOUNTS_PER_LEG,
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        for (leg, accounts) in offered
+            .iter()
+            .zip(remaining_accounts.chunks(MAKE_BASKET_ACCOUNTS_PER_LEG)) 
+        {
+            let token_program = token_program_for(
+                &accounts[0],
+                &self.token_program,
+                &self.token_2022_program,
+            )?;
+            let mint = load_mint(&accounts[0], &leg.mint, token_program.key)?;
+            let maker_ata = &accounts[1];
+            let vault = &accounts[2];
+
+            check_ata(vault, &self.basket.key(), &leg.mint, token_program.key)?;
+
+            create_ata(
+                self.maker.to_account_info(),
+                vault.clone(),
+                self.basket.to_account_info(),
+                mint.to_account_info(),
+                self.system_program.to_account_info(),
+                token_program.clone(),
+                self.associated_token_program.to_account_info(),
+                false,
+            )?;
+
+            transfer_tokens(
+                maker_ata.clone(),
+                vault.clone(),
+                self.maker.to_account_info(),
+                &mint,
+                token_program.clone(),
+                leg.amount,
+                &[],
+            )?;
+        }
+
+        self.basket.set_inner(BasketEscrow {
+            seed,
+            maker: self.maker.key(),
+            offered,
+            requested,
+            fee_bps: self.config.fee_bps,
+            bump: bumps.basket,
+        });
+        Ok(())
+    }
+}
+
+fn check_legs(legs: &[BasketLeg]) -> Result<()> {
+    require!(
+        !legs.is_empty() && legs.len() <= MAX_BASKET_LEGS,
+        EscrowError::InvalidBasket
+    );
+
+    for (i, leg) in legs.iter().enumerate() {
+        require!(leg.amount > 0, EscrowError::InvalidAmount);
+        require!(
+            !legs[..i].iter().any(|other| other.mint == leg.mint),
+            EscrowError::DuplicateMint
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        transfer_checked, Mint, TokenAccount, 
+        TokenInterface, TransferChecked
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{CounterOffer, Escrow},
+};
+
+// The taker proposes a different price for everything left in the escrow,
+// locking their mint B up front so the maker can settle it in one go
+#[derive(Accounts)]
+pub struct MakeCounterOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub taker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        has_one = mint_b,
+        constraint = escrow.allowed_taker.is_none_or(|allowed| allowed == taker.key()) 
+            @ EscrowError::TakerNotAllowed,
+        seeds = [b"escrow", escrow.maker.as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = taker,
+        seeds = [b"counter", escrow.key().as_ref(), taker.key().as_ref()],
+        space = CounterOffer::DISCRIMINATOR.len() + CounterOffer::INIT_SPACE,
+        bump
+    )]
+    pub counter_offer: Account<'info, CounterOffer>,
+    #[account(
+        init,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = counter_offer,
+        associated_token::token_program = token_program
+    )]
+    pub counter_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeCounterOffer<'info> {
+    pub fn make_counter_offer(&mut self, receive: u64, bumps: &MakeCounterOfferBumps) -> Result<()> {
+        require!(receive > 0, EscrowError::InvalidAmount);
+        require!(
+            !self.escrow.is_expired(Clock::get()?.unix_timestamp),
+            EscrowError::OfferExpired
+        );
+
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.counter_vault.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(transfer_ctx, receive, self.mint_b.decimals)?;
+
+        self.counter_vault.reload()?;
+
+        self.counter_offer.set_inner(CounterOffer {
+            escrow: self.escrow.key(),
+            maker: self.escrow.maker,
+            taker: self.taker.key(),
+            mint_a: self.escrow.mint_a,
+            mint_b: self.mint_b.key(),
+            deposit: self.escrow.deposit,
+            receive: self.counter_vault.amount,
+            bump: bumps.counter_offer,
+        });
+        Ok(())
+    }
+}
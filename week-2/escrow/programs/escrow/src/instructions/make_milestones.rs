@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        transfer_checked, Mint, TokenAccount, 
+        TokenInterface, TransferChecked
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{EscrowConfig, Milestone, MilestoneEscrow, MAX_MILESTONES},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeMilestones<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::Paused
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"milestones", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = MilestoneEscrow::DISCRIMINATOR.len() + MilestoneEscrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, MilestoneEscrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeMilestones<'info> {
+    // The vault is funded once with the sum of every milestone
+    pub fn make_milestones(
+        &mut self,
+        seed: u64,
+        payee: Pubkey,
+        milestones: Vec<Milestone>,
+        bumps: &MakeMilestonesBumps,
+    ) -> Result<()> {
+        require!(
+            !milestones.is_empty()
+                && milestones.len() <= MAX_MILESTONES
+                && milestones.iter().all(|milestone| !milestone.released),
+            EscrowError::InvalidMilestones
+        );
+
+        let mut deposit: u64 = 0;
+
+        for milestone in &milestones {
+            require!(milestone.amount > 0, EscrowError::InvalidAmount);
+            deposit = deposit
+                .checked_add(milestone.amount)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(transfer_ctx, deposit, self.mint.decimals)?;
+
+        let mut schedule = [Milestone::default(); MAX_MILESTONES];
+        schedule[..milestones.len()].copy_from_slice(&milestones);
+
+        self.escrow.set_inner(MilestoneEscrow {
+            seed,
+            maker: self.maker.key(),
+            payee,
+            mint: self.mint.key(),
+            milestones: schedule,
+            milestone_count: milestones.len() as u8,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+}
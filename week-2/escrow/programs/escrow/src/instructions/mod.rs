@@ -1,3 +1,9 @@
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod update_config;
+pub use update_config::*;
+
 pub mod make;
 pub use make::*;
 
@@ -5,4 +11,63 @@ pub mod refund;
 pub use refund::*;
 
 pub mod take;
-pub use take::*;
\ No newline at end of file
+pub use take::*;
+
+pub mod update_offer;
+pub use update_offer::*;
+
+pub mod expire;
+pub use expire::*;
+
+pub mod make_basket;
+pub use make_basket::*;
+
+pub mod take_basket;
+pub use take_basket::*;
+
+pub mod refund_basket;
+pub use refund_basket::*;
+pub mod make_asset_offer;
+pub use make_asset_offer::*;
+
+pub mod take_asset_offer;
+pub use take_asset_offer::*;
+
+pub mod refund_asset_offer;
+pub use refund_asset_offer::*;
+
+pub mod make_arbitrated;
+pub use make_arbitrated::*;
+
+pub mod fund_arbitrated;
+pub use fund_arbitrated::*;
+
+pub mod update_arbitrated;
+pub use update_arbitrated::*;
+
+pub mod settle_arbitrated;
+pub use settle_arbitrated::*;
+
+pub mod refund_arbitrated;
+pub use refund_arbitrated::*;
+
+pub mod make_milestones;
+pub use make_milestones::*;
+
+pub mod approve_milestone;
+pub use approve_milestone::*;
+
+pub mod release_milestone;
+pub use release_milestone::*;
+
+pub mod refund_milestones;
+pub use refund_milestones::*;
+
+pub mod make_counter_offer;
+pub use make_counter_offer::*;
+
+pub mod accept_counter_offer;
+pub use accept_counter_offer::*;
+
+pub mod cancel_counter_offer;
+pub use cancel_counter_offer::*;
@@ -8,7 +8,10 @@ use anchor_spl::{
     },
 };
 
-use crate::state::Escrow;
+use crate::{
+    state::{Escrow, OfferBook},
+    utils::harvest_withheld_fees,
+};
 
 #[derive(Accounts)]
 pub struct Refund<'info> {
@@ -16,6 +19,9 @@ pub struct Refund<'info> {
         mut
     )]
     pub maker: Signer<'info>,
+    #[account(
+        mut
+    )]
     pub mint_a: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
@@ -41,6 +47,17 @@ pub struct Refund<'info> {
         associated_token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"book",
+            escrow.mint_a.as_ref(),
+            escrow.mint_b.as_ref(),
+            escrow.book_page.to_le_bytes().as_ref()
+        ],
+        bump = book.bump
+    )]
+    pub book: Account<'info, OfferBook>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -49,6 +66,8 @@ pub struct Refund<'info> {
 
 impl<'info> Refund<'info> {
     pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        self.book.remove(&self.escrow.key())?;
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"escrow",
             self.maker.to_account_info().key.as_ref(),
@@ -71,6 +90,12 @@ impl<'info> Refund<'info> {
 
         transfer_checked(tranfer_ctx, self.vault.amount, self.mint_a.decimals)?;
 
+        harvest_withheld_fees(
+            self.vault.to_account_info(),
+            self.mint_a.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
+
         let close_accounts = CloseAccount {
             account: self.vault.to_account_info(),
             destination: self.maker.to_account_info(),
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, 
+        Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{ArbitratedEscrow, ArbitratedStatus},
+    utils::harvest_withheld_fees,
+};
+
+// Only until a taker has funded, after that the escrow settles through
+// release or the arbiter
+#[derive(Accounts)]
+pub struct RefundArbitrated<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint_a,
+        has_one = maker,
+        constraint = escrow.status == ArbitratedStatus::Open @ EscrowError::InvalidStatus,
+        seeds = [b"arbitrated", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, ArbitratedEscrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundArbitrated<'info> {
+    pub fn refund_arbitrated(&mut self) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"arbitrated",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.maker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.vault_a.amount, self.mint_a.decimals)?;
+
+        harvest_withheld_fees(
+            self.vault_a.to_account_info(),
+            self.mint_a.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
+
+        let close_accounts = CloseAccount {
+            account: self.vault_a.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
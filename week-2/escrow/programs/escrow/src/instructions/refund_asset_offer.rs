@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::TokenAccount,
+};
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    errors::EscrowError,
+    instructions::ASSET_LEG_ACCOUNTS,
+    state::{AssetEscrow, OfferLeg},
+    utils::{
+        check_asset, check_ata, close_token_account, create_ata, load_mint, token_program_for,
+        transfer_asset, transfer_tokens,
+    },
+};
+
+pub const REFUND_ASSET_OFFER_TOKEN_ACCOUNTS: usize = 3;
+
+// Remaining accounts, for the offered leg:
+// token: [mint (writable), vault, maker's associated token account]
+// asset: [asset, collection or the core program when there is none]
+#[derive(Accounts)]
+pub struct RefundAssetOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"asset_escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, AssetEscrow>,
+
+    #[account(
+        address = CORE_PROGRAM_ID
+    )]
+    /// CHECK: This is the MPL_CORE_PROGRAM
+    pub core_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundAssetOffer<'info> {
+    pub fn refund_asset_offer(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"asset_escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        match &self.escrow.offered {
+            OfferLeg::Token { mint, .. } => {
+                require_eq!(
+                    remaining_accounts.len(),
+                    REFUND_ASSET_OFFER_TOKEN_ACCOUNTS,
+                    EscrowError::InvalidRemainingAccounts
+                );
+
+                let token_program = token_program_for(
+                    &remaining_accounts[0],
+                    &self.token_program,
+                    &self.token_2022_program,
+                )?;
+                let mint = load_mint(&remaining_accounts[0], mint, token_program.key)?;
+                let vault = &remaining_accounts[1];
+                let maker_ata = &remaining_accounts[2];
+
+                check_ata(vault, &self.escrow.key(), &mint.key(), token_program.key)?;
+                check_ata(maker_ata, &self.maker.key(), &mint.key(), token_program.key)?;
+
+                create_ata(
+                    self.maker.to_account_info(),
+                    maker_ata.clone(),
+                    self.maker.to_account_info(),
+                    mint.to_account_info(),
+                    self.system_program.to_account_info(),
+                    token_program.clone(),
+                    self.associated_token_program.to_account_info(),
+                    true,
+                )?;
+
+                let vault_amount = InterfaceAccount::<TokenAccount>::try_from(vault)?.amount;
+
+                transfer_tokens(
+                    vault.clone(),
+                    maker_ata.clone(),
+                    self.escrow.to_account_info(),
+                    &mint,
+                    token_program.clone(),
+                    vault_amount,
+                    signer_seeds,
+                )?;
+
+                close_token_account(
+                    vault.clone(),
+                    mint.to_account_info(),
+                    self.maker.to_account_info(),
+                    self.escrow.to_account_info(),
+                    token_program.clone(),
+                    signer_seeds,
+                )
+            }
+            OfferLeg::Asset { asset, collection } => {
+                require_eq!(
+                    remaining_accounts.len(),
+                    ASSET_LEG_ACCOUNTS,
+                    EscrowError::InvalidRemainingAccounts
+                );
+
+                let asset_info = &remaining_accounts[0];
+                let collection_info = &remaining_accounts[1];
+
+                check_asset(asset_info, collection_info, asset, collection)?;
+
+                transfer_asset(
+                    asset_info,
+                    collection.is_some().then_some(collection_info),
+                    &self.maker.to_account_info(),
+                    &self.escrow.to_account_info(),
+                    &self.maker.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    &self.core_program.to_account_info(),
+                    signer_seeds,
+                )
+            }
+        }
+    }
+}
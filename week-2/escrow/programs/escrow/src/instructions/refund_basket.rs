@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::TokenAccount,
+};
+
+use crate::{
+    errors::EscrowError,
+    state::BasketEscrow,
+    utils::{
+        check_ata, close_token_account, create_ata, load_mint, token_program_for,
+        transfer_tokens,
+    },
+};
+
+pub const REFUND_BASKET_ACCOUNTS_PER_LEG: usize = 3;
+
+// Remaining accounts, for every offered leg in order: 
+// [mint (writable), vault, maker's associated token account]
+#[derive(Accounts)]
+pub struct RefundBasket<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"basket", maker.key().as_ref(), &basket.seed.to_le_bytes()],
+        bump = basket.bump
+    )]
+    pub basket: Account<'info, BasketEscrow>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundBasket<'info> {
+    pub fn refund_basket(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require_eq!(
+            remaining_accounts.len(),
+            self.basket.offered.len() * REFUND_BASKET_ACCOUNTS_PER_LEG,
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"basket",
+            self.maker.to_account_info().key.as_ref(),
+            &self.basket.seed.to_le_bytes(),
+            &[self.basket.bump],
+        ]];
+
+        for (leg, accounts) in self.basket
+            .offered
+            .iter()
+            .zip(remaining_accounts.chunks(REFUND_BASKET_ACCOUNTS_PER_LEG)) 
+        {
+            let token_program = token_program_for(
+                &accounts[0],
+                &self.token_program,
+                &self.token_2022_program,
+            )?;
+            let mint = load_mint(&accounts[0], &leg.mint, token_program.key)?;
+            let vault = &accounts[1];
+            let maker_ata = &accounts[2];
+
+            check_ata(vault, &self.basket.key(), &leg.mint, token_program.key)?;
+            check_ata(maker_ata, &self.maker.key(), &leg.mint, token_program.key)?;
+
+            create_ata(
+                self.maker.to_account_info(),
+                maker_ata.clone(),
+                self.maker.to_account_info(),
+                mint.to_account_info(),
+                self.system_program.to_account_info(),
+                token_program.clone(),
+                self.associated_token_program.to_account_info(),
+                true,
+            )?;
+
+            let vault_amount = InterfaceAccount::<TokenAccount>::try_from(vault)?.amount;
+
+            transfer_tokens(
+                vault.clone(),
+                maker_ata.clone(),
+                self.basket.to_account_info(),
+                &mint,
+                token_program.clone(),
+                vault_amount,
+                signer_seeds,
+            )?;
+
+            close_token_account(
+                vault.clone(),
+                mint.to_account_info(),
+                self.maker.to_account_info(),
+                self.basket.to_account_info(),
+                token_program.clone(),
+                signer_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
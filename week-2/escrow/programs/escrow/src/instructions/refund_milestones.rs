@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, 
+        Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::MilestoneEscrow,
+    utils::harvest_withheld_fees,
+};
+
+// The maker can take back whatever is left, but not while a milestone the
+// payee has already earned is waiting to be released. Milestones that unlock
+// by timestamp also need the payee to sign off on the refund
+#[derive(Accounts)]
+pub struct RefundMilestones<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    pub payee: Option<Signer<'info>>,
+    #[account(
+        mut
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint,
+        has_one = maker,
+        seeds = [b"milestones", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, MilestoneEscrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundMilestones<'info> {
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !self.escrow.active().iter().any(|milestone| milestone.is_releasable(now)),
+            EscrowError::MilestonePending
+        );
+
+        if self.escrow.has_time_locked() {
+            require!(
+                self.payee
+                    .as_ref()
+                    .is_some_and(|payee| payee.key() == self.escrow.payee),
+                EscrowError::PayeeSignatureRequired
+            );
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"milestones",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.maker_ata.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.vault.amount, self.mint.decimals)?;
+
+        harvest_withheld_fees(
+            self.vault.to_account_info(),
+            self.mint.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
+
+        let close_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
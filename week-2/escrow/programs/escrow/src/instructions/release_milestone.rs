@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, 
+        Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    state::MilestoneEscrow,
+    utils::harvest_withheld_fees,
+};
+
+// Permissionless, anyone can push an approved or unlocked milestone to the
+// payee. The last one sweeps whatever is left in the vault to the payee and
+// closes the escrow back to the maker
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
+    #[account(
+        mut
+    )]
+    pub caller: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub maker: SystemAccount<'info>,
+    pub payee: SystemAccount<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint,
+        associated_token::authority = payee,
+        associated_token::token_program = token_program
+    )]
+    pub payee_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = payee,
+        has_one = mint,
+        seeds = [b"milestones", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, MilestoneEscrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReleaseMilestone<'info> {
+    pub fn release_milestone(&mut self, index: u8) -> Result<()> {
+        require!(index < self.escrow.milestone_count, EscrowError::InvalidMilestone);
+
+        let milestone = self.escrow.milestones[usize::from(index)];
+
+        require!(!milestone.released, EscrowError::MilestoneReleased);
+        require!(
+            milestone.is_releasable(Clock::get()?.unix_timestamp),
+            EscrowError::NotReleasable
+        );
+
+        self.escrow.milestones[usize::from(index)].released = true;
+
+        let is_last = self.escrow.active().iter().all(|milestone| milestone.released);
+
+        // Transfer fees and stray deposits mean the vault rarely holds exactly
+        // the last tranche, the close would fail on anything left over
+        let amount = if is_last {
+            self.vault.amount
+        } else {
+            milestone.amount
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"milestones",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.payee_ata.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, amount, self.mint.decimals)?;
+
+        if is_last {
+            harvest_withheld_fees(
+                self.vault.to_account_info(),
+                self.mint.to_account_info(),
+                self.token_program.to_account_info(),
+            )?;
+
+            let close_accounts = CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let close_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                close_accounts,
+                signer_seeds,
+            );
+
+            close_account(close_ctx)?;
+            self.escrow.close(self.maker.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{protocol_fee, ArbitratedEscrow, ArbitratedStatus, EscrowConfig, Party},
+    utils::{check_ata, close_token_account, create_ata, transfer_tokens},
+};
+
+// Shared by release_arbitrated and resolve_dispute. Releasing swaps the
+// vaults, mint A to the taker and mint B to the maker less the protocol fee.
+// A ruling for the maker unwinds it instead, both sides get their deposit back
+#[derive(Accounts)]
+pub struct SettleArbitrated<'info> {
+    #[account(
+        mut
+    )]
+    pub caller: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mut,
+        constraint = escrow.taker == Some(taker.key()) @ EscrowError::NotAParty
+    )]
+    pub taker: SystemAccount<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, EscrowConfig>>,
+    #[account(
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    // Checked and created only when a fee is charged
+    #[account(
+        mut
+    )]
+    /// CHECK: The treasury's associated token account for mint B
+    pub treasury_ata_b: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [b"arbitrated", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, ArbitratedEscrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleArbitrated<'info> {
+    pub fn release_arbitrated(&mut self) -> Result<()> {
+        require!(
+            self.escrow.can_release(Clock::get()?.unix_timestamp),
+            EscrowError::NotReleasable
+        );
+
+        self.settle(Party::Taker)
+    }
+
+    pub fn resolve_dispute(&mut self, winner: Party) -> Result<()> {
+        require_keys_eq!(self.caller.key(), self.escrow.arbiter, EscrowError::NotArbiter);
+        require!(
+            self.escrow.status == ArbitratedStatus::Disputed,
+            EscrowError::InvalidStatus
+        );
+
+        self.settle(winner)
+    }
+
+    fn settle(&self, winner: Party) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"arbitrated",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let (to_a, to_b) = match winner {
+            Party::Taker => (&self.taker_ata_a, &self.maker_ata_b),
+            Party::Maker => (&self.maker_ata_a, &self.taker_ata_b),
+        };
+
+        transfer_tokens(
+            self.vault_a.to_account_info(),
+            to_a.to_account_info(),
+            self.escrow.to_account_info(),
+            &self.mint_a,
+            self.token_program.to_account_info(),
+            self.vault_a.amount,
+            signer_seeds,
+        )?;
+
+        // The protocol fee is only taken when the swap goes through
+        let fee = match winner {
+            Party::Taker => protocol_fee(self.vault_b.amount, self.escrow.fee_bps)?,
+            Party::Maker => 0,
+        };
+
+        if fee > 0 {
+            check_ata(
+                &self.treasury_ata_b,
+                &self.treasury.key(),
+                &self.mint_b.key(),
+                &self.token_program.key(),
+            )?;
+
+            create_ata(
+                self.caller.to_account_info(),
+                self.treasury_ata_b.to_account_info(),
+                self.treasury.to_account_info(),
+                self.mint_b.to_account_info(),
+                self.system_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.associated_token_program.to_account_info(),
+                true,
+            )?;
+
+            transfer_tokens(
+                self.vault_b.to_account_info(),
+                self.treasury_ata_b.to_account_info(),
+                self.escrow.to_account_info(),
+                &self.mint_b,
+                self.token_program.to_account_info(),
+                fee,
+                signer_seeds,
+            )?;
+        }
+
+        transfer_tokens(
+            self.vault_b.to_account_info(),
+            to_b.to_account_info(),
+            self.escrow.to_account_info(),
+            &self.mint_b,
+            self.token_program.to_account_info(),
+            self.vault_b.amount - fee,
+            signer_seeds,
+        )?;
+
+        close_token_account(
+            self.vault_a.to_account_info(),
+            self.mint_a.to_account_info(),
+            self.maker.to_account_info(),
+            self.escrow.to_account_info(),
+            self.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        // The taker paid for the mint B vault
+        close_token_account(
+            self.vault_b.to_account_info(),
+            self.mint_b.to_account_info(),
+            self.taker.to_account_info(),
+            self.escrow.to_account_info(),
+            self.token_program.to_account_info(),
+            signer_seeds,
+        )
+    }
+}
@@ -8,21 +8,30 @@ use anchor_spl::{
     },
 };
 
-use crate::Escrow;
+use crate::{
+    errors::EscrowError,
+    state::protocol_fee,
+    utils::{amount_to_send, check_ata, create_ata, harvest_withheld_fees},
+    Escrow, EscrowConfig, OfferBook,
+};
 
 #[derive(Accounts)]
 pub struct Take<'info> {
     #[account(mut)]
     pub taker: Signer<'info>,
+    #[account(
+        mut
+    )]
     pub maker: SystemAccount<'info>,
     #[account(
+        mut,
         mint::token_program = token_program
     )]
-    pub mint_a: InterfaceAccount<'info, Mint>,
-        #[account(
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
         mint::token_program = token_program
     )]
-    pub mint_b: InterfaceAccount<'info, Mint>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         init_if_needed,
         payer = taker,
@@ -30,14 +39,14 @@ pub struct Take<'info> {
         associated_token::authority = taker,
         associated_token::token_program = token_program
     )]
-    pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = mint_b,
         associated_token::authority = taker,
         associated_token::token_program = token_program
     )]
-    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init_if_needed,
         payer = taker,
@@ -45,30 +54,136 @@ pub struct Take<'info> {
         associated_token::authority = maker,
         associated_token::token_program = token_program
     )]
-    pub maker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, EscrowConfig>>,
+    #[account(
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    // Checked and created only when a fee is charged
+    #[account(
+        mut
+    )]
+    /// CHECK: The treasury's associated token account for mint B
+    pub treasury_ata_b: UncheckedAccount<'info>,
+    // Only closed once the offer is completely filled
     #[account(
         mut,
-        close = maker,
         has_one = mint_a,
+        has_one = mint_b,
         has_one = maker,
+        constraint = escrow.allowed_taker.is_none_or(|allowed| allowed == taker.key()) 
+            @ EscrowError::TakerNotAllowed,
         seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
         bump = escrow.bump
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
     #[account(
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = escrow,
         associated_token::token_program = token_program
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"book",
+            escrow.mint_a.as_ref(),
+            escrow.mint_b.as_ref(),
+            escrow.book_page.to_le_bytes().as_ref()
+        ],
+        bump = book.bump
+    )]
+    pub book: Box<Account<'info, OfferBook>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>    
 }
 
 impl<'info> Take<'info> {
-    pub fn deposit(&self) -> Result<()> {
+    pub fn take(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!self.escrow.is_expired(now), EscrowError::OfferExpired);
+
+        self.escrow.refresh_receive(now);
+
+        let receive = self.escrow.receive_for(amount)?;
+
+        self.deposit(receive)?;
+
+        // The last fill sweeps the vault, so tokens sent to it from outside can't block the close
+        if amount == self.escrow.deposit {
+            self.withdraw(self.vault.amount)?;
+        } else {
+            self.withdraw(amount)?;
+        }
+
+        self.escrow.fill(amount, receive)?;
+
+        if self.escrow.deposit == 0 {
+            self.book.remove(&self.escrow.key())?;
+            self.close_vault()?;
+            self.escrow.close(self.maker.to_account_info())?;
+        } else {
+            self.book
+                .update(&self.escrow.key(), self.escrow.deposit, self.escrow.receive)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn deposit(&self, receive: u64) -> Result<()> {
+        // The protocol fee comes out of what the maker receives
+        let fee = protocol_fee(receive, self.escrow.fee_bps)?;
+
+        // Net offers have the taker cover the mint's transfer fee on top
+        let mint_b = self.mint_b.to_account_info();
+        let fee_mode = self.escrow.fee_mode;
+
+        if fee > 0 {
+            check_ata(
+                &self.treasury_ata_b,
+                &self.treasury.key(),
+                &self.mint_b.key(),
+                &self.token_program.key(),
+            )?;
+
+            create_ata(
+                self.taker.to_account_info(),
+                self.treasury_ata_b.to_account_info(),
+                self.treasury.to_account_info(),
+                self.mint_b.to_account_info(),
+                self.system_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.associated_token_program.to_account_info(),
+                true,
+            )?;
+
+            let fee_accounts = TransferChecked {
+                from: self.taker_ata_b.to_account_info(),
+                to: self.treasury_ata_b.to_account_info(),
+                authority: self.taker.to_account_info(),
+                mint: self.mint_b.to_account_info()
+            };
+
+            let fee_ctx = CpiContext::new(
+                self.token_program.to_account_info(),
+                fee_accounts
+            );
+
+            transfer_checked(
+                fee_ctx,
+                amount_to_send(&mint_b, fee, fee_mode)?,
+                self.mint_b.decimals
+            )?;
+        }
+
         let transfer_accounts = TransferChecked {
             from: self.taker_ata_b.to_account_info(),
             to: self.maker_ata_b.to_account_info(),
@@ -81,10 +196,14 @@ impl<'info> Take<'info> {
             transfer_accounts
         );
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)
+        transfer_checked(
+            cpi_ctx,
+            amount_to_send(&mint_b, receive - fee, fee_mode)?,
+            self.mint_b.decimals
+        )
     }
 
-    pub fn withdraw_and_close_vault(&self) -> Result<()> {
+    pub fn withdraw(&self, amount: u64) -> Result<()> {
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"escrow",
             self.maker.to_account_info().key.as_ref(),
@@ -105,7 +224,22 @@ impl<'info> Take<'info> {
             signer_seeds,
         );
 
-        transfer_checked(transfer_ctx, self.vault.amount, self.mint_a.decimals)?;
+        transfer_checked(transfer_ctx, amount, self.mint_a.decimals)
+    }
+
+    pub fn close_vault(&self) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        harvest_withheld_fees(
+            self.vault.to_account_info(),
+            self.mint_a.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
 
         let close_accounts = CloseAccount {
             account: self.vault.to_account_info(),
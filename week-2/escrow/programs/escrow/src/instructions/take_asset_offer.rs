@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::TokenAccount,
+};
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    errors::EscrowError,
+    instructions::ASSET_LEG_ACCOUNTS,
+    state::{protocol_fee, AssetEscrow, EscrowConfig, OfferLeg},
+    utils::{
+        check_asset, check_ata, close_token_account, create_ata, load_mint, token_program_for,
+        transfer_asset, transfer_tokens,
+    },
+};
+
+pub const TAKE_ASSET_OFFER_OFFERED_TOKEN_ACCOUNTS: usize = 3;
+pub const TAKE_ASSET_OFFER_REQUESTED_TOKEN_ACCOUNTS: usize = 4;
+
+// Remaining accounts, for the offered leg:
+// token: [mint (writable), vault, taker's associated token account]
+// asset: [asset, collection or the core program when there is none]
+// followed by, for the requested leg:
+// token: [mint, taker's token account, maker's associated token account, treasury's associated token account]
+// asset: [asset, collection or the core program when there is none]
+#[derive(Accounts)]
+pub struct TakeAssetOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub taker: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"asset_escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, AssetEscrow>,
+
+    #[account(
+        address = CORE_PROGRAM_ID
+    )]
+    /// CHECK: This is the MPL_CORE_PROGRAM
+    pub core_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeAssetOffer<'info> {
+    pub fn take_asset_offer(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let offered_len = match self.escrow.offered {
+            OfferLeg::Token { .. } => TAKE_ASSET_OFFER_OFFERED_TOKEN_ACCOUNTS,
+            OfferLeg::Asset { .. } => ASSET_LEG_ACCOUNTS,
+        };
+        let requested_len = match self.escrow.requested {
+            OfferLeg::Token { .. } => TAKE_ASSET_OFFER_REQUESTED_TOKEN_ACCOUNTS,
+            OfferLeg::Asset { .. } => ASSET_LEG_ACCOUNTS,
+        };
+
+        require_eq!(
+            remaining_accounts.len(),
+            offered_len + requested_len,
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        let (offered_accounts, requested_accounts) = remaining_accounts.split_at(offered_len);
+
+        self.pay_requested(requested_accounts)?;
+        self.release_offered(offered_accounts)
+    }
+
+    fn pay_requested(&self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        match &self.escrow.requested {
+            OfferLeg::Token { mint, amount } => {
+                let token_program = token_program_for(
+                    &accounts[0],
+                    &self.token_program,
+                    &self.token_2022_program,
+                )?;
+                let mint = load_mint(&accounts[0], mint, token_program.key)?;
+                let taker_ata = &accounts[1];
+                let maker_ata = &accounts[2];
+                let treasury_ata = &accounts[3];
+
+                check_ata(maker_ata, &self.maker.key(), &mint.key(), token_program.key)?;
+                check_ata(treasury_ata, &self.treasury.key(), &mint.key(), token_program.key)?;
+
+                // The protocol fee comes out of what the maker receives, assets
+                // are never charged one
+                let fee = protocol_fee(*amount, self.escrow.fee_bps)?;
+
+                if fee > 0 {
+                    create_ata(
+                        self.taker.to_account_info(),
+                        treasury_ata.clone(),
+                        self.treasury.to_account_info(),
+                        mint.to_account_info(),
+                        self.system_program.to_account_info(),
+                        token_program.clone(),
+                        self.associated_token_program.to_account_info(),
+                        true,
+                    )?;
+
+                    transfer_tokens(
+                        taker_ata.clone(),
+                        treasury_ata.clone(),
+                        self.taker.to_account_info(),
+                        &mint,
+                        token_program.clone(),
+                        fee,
+                        &[],
+                    )?;
+                }
+
+                create_ata(
+                    self.taker.to_account_info(),
+                    maker_ata.clone(),
+                    self.maker.to_account_info(),
+                    mint.to_account_info(),
+                    self.system_program.to_account_info(),
+                    token_program.clone(),
+                    self.associated_token_program.to_account_info(),
+                    true,
+                )?;
+
+                transfer_tokens(
+                    taker_ata.clone(),
+                    maker_ata.clone(),
+                    self.taker.to_account_info(),
+                    &mint,
+                    token_program.clone(),
+                    amount - fee,
+                    &[],
+                )
+            }
+            OfferLeg::Asset { asset, collection } => {
+                let asset_info = &accounts[0];
+                let collection_info = &accounts[1];
+
+                check_asset(asset_info, collection_info, asset, collection)?;
+
+                transfer_asset(
+                    asset_info,
+                    collection.is_some().then_some(collection_info),
+                    &self.taker.to_account_info(),
+                    &self.taker.to_account_info(),
+                    &self.maker.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    &self.core_program.to_account_info(),
+                    &[],
+                )
+            }
+        }
+    }
+
+    fn release_offered(&self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"asset_escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        match &self.escrow.offered {
+            OfferLeg::Token { mint, .. } => {
+                let token_program = token_program_for(
+                    &accounts[0],
+                    &self.token_program,
+                    &self.token_2022_program,
+                )?;
+                let mint = load_mint(&accounts[0], mint, token_program.key)?;
+                let vault = &accounts[1];
+                let taker_ata = &accounts[2];
+
+                check_ata(vault, &self.escrow.key(), &mint.key(), token_program.key)?;
+                check_ata(taker_ata, &self.taker.key(), &mint.key(), token_program.key)?;
+
+                create_ata(
+                    self.taker.to_account_info(),
+                    taker_ata.clone(),
+                    self.taker.to_account_info(),
+                    mint.to_account_info(),
+                    self.system_program.to_account_info(),
+                    token_program.clone(),
+                    self.associated_token_program.to_account_info(),
+                    true,
+                )?;
+
+                let vault_amount = InterfaceAccount::<TokenAccount>::try_from(vault)?.amount;
+
+                transfer_tokens(
+                    vault.clone(),
+                    taker_ata.clone(),
+                    self.escrow.to_account_info(),
+                    &mint,
+                    token_program.clone(),
+                    vault_amount,
+                    signer_seeds,
+                )?;
+
+                close_token_account(
+                    vault.clone(),
+                    mint.to_account_info(),
+                    self.maker.to_account_info(),
+                    self.escrow.to_account_info(),
+                    token_program.clone(),
+                    signer_seeds,
+                )
+            }
+            OfferLeg::Asset { asset, collection } => {
+                let asset_info = &accounts[0];
+                let collection_info = &accounts[1];
+
+                check_asset(asset_info, collection_info, asset, collection)?;
+
+                transfer_asset(
+                    asset_info,
+                    collection.is_some().then_some(collection_info),
+                    &self.taker.to_account_info(),
+                    &self.escrow.to_account_info(),
+                    &self.taker.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    &self.core_program.to_account_info(),
+                    signer_seeds,
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::TokenAccount,
+};
+
+use crate::{
+    errors::EscrowError,
+    state::{protocol_fee, BasketEscrow, EscrowConfig},
+    utils::{
+        check_ata, close_token_account, create_ata, load_mint, token_program_for,
+        transfer_tokens,
+    },
+};
+
+pub const TAKE_BASKET_OFFERED_ACCOUNTS_PER_LEG: usize = 3;
+pub const TAKE_BASKET_REQUESTED_ACCOUNTS_PER_LEG: usize = 4;
+
+// Remaining accounts, for every offered leg in order:
+// [mint (writable), vault, taker's associated token account]
+// followed by, for every requested leg in order:
+// [mint, taker's token account, maker's associated token account, treasury's associated token account]
+#[derive(Accounts)]
+pub struct TakeBasket<'info> {
+    #[account(
+        mut
+    )]
+    pub taker: Signer<'info>,
+    #[account(
+        mut
+    )]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"basket", maker.key().as_ref(), &basket.seed.to_le_bytes()],
+        bump = basket.bump
+    )]
+    pub basket: Account<'info, BasketEscrow>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeBasket<'info> {
+    pub fn take_basket(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let offered_len = self.basket.offered.len() * TAKE_BASKET_OFFERED_ACCOUNTS_PER_LEG;
+
+        require_eq!(
+            remaining_accounts.len(),
+            offered_len + self.basket.requested.len() * TAKE_BASKET_REQUESTED_ACCOUNTS_PER_LEG,
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        let (offered_accounts, requested_accounts) = remaining_accounts.split_at(offered_len);
+
+        self.pay_requested(requested_accounts)?;
+        self.withdraw_offered_and_close_vaults(offered_accounts)
+    }
+
+    fn pay_requested(&self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        for (leg, accounts) in self.basket
+            .requested
+            .iter()
+            .zip(accounts.chunks(TAKE_BASKET_REQUESTED_ACCOUNTS_PER_LEG)) 
+        {
+            let token_program = token_program_for(
+                &accounts[0],
+                &self.token_program,
+                &self.token_2022_program,
+            )?;
+            let mint = load_mint(&accounts[0], &leg.mint, token_program.key)?;
+            let taker_ata = &accounts[1];
+            let maker_ata = &accounts[2];
+            let treasury_ata = &accounts[3];
+
+            check_ata(maker_ata, &self.maker.key(), &leg.mint, token_program.key)?;
+            check_ata(treasury_ata, &self.treasury.key(), &leg.mint, token_program.key)?;
+
+            // The protocol fee comes out of what the maker receives
+            let fee = protocol_fee(leg.amount, self.basket.fee_bps)?;
+
+            if fee > 0 {
+                create_ata(
+                    self.taker.to_account_info(),
+                    treasury_ata.clone(),
+                    self.treasury.to_account_info(),
+                    mint.to_account_info(),
+                    self.system_program.to_account_info(),
+                    token_program.clone(),
+                    self.associated_token_program.to_account_info(),
+                    true,
+                )?;
+
+                transfer_tokens(
+                    taker_ata.clone(),
+                    treasury_ata.clone(),
+                    self.taker.to_account_info(),
+                    &mint,
+                    token_program.clone(),
+                    fee,
+                    &[],
+                )?;
+            }
+
+            create_ata(
+                self.taker.to_account_info(),
+                maker_ata.clone(),
+                self.maker.to_account_info(),
+                mint.to_account_info(),
+                self.system_program.to_account_info(),
+                token_program.clone(),
+                self.associated_token_program.to_account_info(),
+                true,
+            )?;
+
+            transfer_tokens(
+                taker_ata.clone(),
+                maker_ata.clone(),
+                self.taker.to_account_info(),
+                &mint,
+                token_program.clone(),
+                leg.amount - fee,
+                &[],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn withdraw_offered_and_close_vaults(&self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"basket",
+            self.maker.to_account_info().key.as_ref(),
+            &self.basket.seed.to_le_bytes(),
+            &[self.basket.bump],
+        ]];
+
+        for (leg, accounts) in self.basket
+            .offered
+            .iter()
+            .zip(accounts.chunks(TAKE_BASKET_OFFERED_ACCOUNTS_PER_LEG)) 
+        {
+            let token_program = token_program_for(
+                &accounts[0],
+                &self.token_program,
+                &self.token_2022_program,
+            )?;
+            let mint = load_mint(&accounts[0], &leg.mint, token_program.key)?;
+            let vault = &accounts[1];
+            let taker_ata = &accounts[2];
+
+            check_ata(vault, &self.basket.key(), &leg.mint, token_program.key)?;
+            check_ata(taker_ata, &self.taker.key(), &leg.mint, token_program.key)?;
+
+            create_ata(
+                self.taker.to_account_info(),
+                taker_ata.clone(),
+                self.taker.to_account_info(),
+                mint.to_account_info(),
+                self.system_program.to_account_info(),
+                token_program.clone(),
+                self.associated_token_program.to_account_info(),
+                true,
+            )?;
+
+            let vault_amount = InterfaceAccount::<TokenAccount>::try_from(vault)?.amount;
+
+            transfer_tokens(
+                vault.clone(),
+                taker_ata.clone(),
+                self.basket.to_account_info(),
+                &mint,
+                token_program.clone(),
+                vault_amount,
+                signer_seeds,
+            )?;
+
+            close_token_account(
+                vault.clone(),
+                mint.to_account_info(),
+                self.maker.to_account_info(),
+                self.basket.to_account_info(),
+                token_program.clone(),
+                signer_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
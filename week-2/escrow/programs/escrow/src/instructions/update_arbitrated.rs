@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::EscrowError,
+    state::{ArbitratedEscrow, ArbitratedStatus, Party},
+};
+
+#[derive(Accounts)]
+pub struct UpdateArbitrated<'info> {
+    pub party: Signer<'info>,
+    #[account(
+        mut,
+        constraint = escrow.status == ArbitratedStatus::Funded @ EscrowError::InvalidStatus,
+        seeds = [b"arbitrated", escrow.maker.as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, ArbitratedEscrow>,
+}
+
+impl<'info> UpdateArbitrated<'info> {
+    pub fn confirm_arbitrated(&mut self) -> Result<()> {
+        match self.escrow.party(&self.party.key()) {
+            Some(Party::Maker) => self.escrow.maker_confirmed = true,
+            Some(Party::Taker) => self.escrow.taker_confirmed = true,
+            None => return err!(EscrowError::NotAParty),
+        }
+
+        Ok(())
+    }
+
+    // Hands the escrow over to the arbiter, as long as it isn't already
+    // releasable
+    pub fn raise_dispute(&mut self) -> Result<()> {
+        require!(
+            self.escrow.party(&self.party.key()).is_some(),
+            EscrowError::NotAParty
+        );
+        require!(
+            !self.escrow.can_release(Clock::get()?.unix_timestamp),
+            EscrowError::InvalidStatus
+        );
+
+        self.escrow.status = ArbitratedStatus::Disputed;
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::EscrowError,
+    state::{EscrowConfig, MAX_FEE_BPS},
+};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_fee(&mut self, fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, EscrowError::InvalidFee);
+
+        self.config.fee_bps = fee_bps;
+        Ok(())
+    }
+
+    pub fn update_treasury(&mut self, treasury: Pubkey) -> Result<()> {
+        self.config.treasury = treasury;
+        Ok(())
+    }
+
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.config.paused = paused;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        transfer_checked, Mint, TokenAccount, 
+        TokenInterface, TransferChecked
+    },
+};
+
+use crate::{
+    errors::EscrowError,
+    events::OfferUpdatedEvent,
+    state::{Escrow, OfferBook},
+    utils::amount_to_send,
+};
+
+#[derive(Accounts)]
+pub struct UpdateOffer<'info> {
+    #[account(
+        mut
+    )]
+    pub maker: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint_a,
+        has_one = maker,
+        seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"book",
+            escrow.mint_a.as_ref(),
+            escrow.mint_b.as_ref(),
+            escrow.book_page.to_le_bytes().as_ref()
+        ],
+        bump = book.bump
+    )]
+    pub book: Account<'info, OfferBook>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateOffer<'info> {
+    // Fields left as None keep their current value, the deposit is moved in or
+    // out of the vault to match the new amount
+    pub fn update_offer(
+        &mut self,
+        deposit: Option<u64>,
+        receive: Option<u64>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        if let Some(deposit) = deposit {
+            require!(deposit > 0, EscrowError::InvalidAmount);
+
+            if deposit > self.escrow.deposit {
+                self.top_up(deposit - self.escrow.deposit)?;
+            } else if deposit < self.escrow.deposit {
+                self.withdraw(self.escrow.deposit - deposit)?;
+            }
+
+            // Gross top ups of mints with a transfer fee land short of `deposit`
+            self.vault.reload()?;
+            let previous = self.escrow.deposit;
+            self.escrow.deposit = self.vault.amount;
+
+            // Auction prices are for the whole deposit, so they follow it up or down
+            if let Some(auction) = &mut self.escrow.auction {
+                auction.scale(self.vault.amount, previous)?;
+            }
+        }
+
+        if let Some(receive) = receive {
+            require!(self.escrow.auction.is_none(), EscrowError::AuctionPriced);
+            require!(receive > 0, EscrowError::InvalidAmount);
+            self.escrow.receive = receive;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+
+        if let Some(expires_at) = expires_at {
+            require!(expires_at > now, EscrowError::InvalidExpiry);
+            self.escrow.expires_at = Some(expires_at);
+        }
+
+        self.escrow.refresh_receive(now);
+
+        self.book
+            .update(&self.escrow.key(), self.escrow.deposit, self.escrow.receive)?;
+
+        emit!(OfferUpdatedEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            deposit: self.escrow.deposit,
+            receive: self.escrow.receive,
+            expires_at: self.escrow.expires_at,
+        });
+
+        Ok(())
+    }
+
+    fn top_up(&mut self, amount: u64) -> Result<()> {
+        let amount = amount_to_send(
+            &self.mint_a.to_account_info(),
+            amount,
+            self.escrow.fee_mode
+        )?;
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(transfer_ctx, amount, self.mint_a.decimals)
+    }
+
+    fn withdraw(&mut self, amount: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.maker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, amount, self.mint_a.decimals)
+    }
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 
+pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 pub use instructions::*;
 pub use state::*;
@@ -11,17 +14,186 @@ declare_id!("DZX1SPFX65UaRUYExGTsybjUn93SgcDhzAJgJR6BQd8L");
 #[program]
 pub mod escrow {
     use super::*;
-    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
-        ctx.accounts.deposit(deposit)?;
-        ctx.accounts.init_escrow(seed, receive, &ctx.bumps)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.initialize_config(fee_bps, treasury, &ctx.bumps)
+    }
+
+    pub fn update_fee(ctx: Context<UpdateConfig>, fee_bps: u16) -> Result<()> {
+        ctx.accounts.update_fee(fee_bps)
+    }
+
+    pub fn update_treasury(ctx: Context<UpdateConfig>, treasury: Pubkey) -> Result<()> {
+        ctx.accounts.update_treasury(treasury)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn make(
+        ctx: Context<Make>,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+        expires_at: Option<i64>,
+        allowed_taker: Option<Pubkey>,
+        auction: Option<DutchAuction>,
+        fee_mode: TransferFeeMode,
+        book_page: u32,
+    ) -> Result<()> {
+        ctx.accounts.deposit(deposit, fee_mode)?;
+        ctx.accounts.init_escrow(
+            seed,
+            receive,
+            expires_at,
+            allowed_taker,
+            auction,
+            fee_mode,
+            book_page,
+            &ctx.bumps,
+        )?;
+        ctx.accounts.list_offer(book_page, &ctx.bumps)
     }
 
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
         ctx.accounts.refund_and_close_vault()
     }
 
-    pub fn take(ctx: Context<Take>) -> Result<()> {
-        ctx.accounts.deposit()?;
-        ctx.accounts.withdraw_and_close_vault()
+    pub fn take(ctx: Context<Take>, amount: u64) -> Result<()> {
+        ctx.accounts.take(amount)
+    }
+
+    pub fn update_offer(
+        ctx: Context<UpdateOffer>,
+        deposit: Option<u64>,
+        receive: Option<u64>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts.update_offer(deposit, receive, expires_at)
+    }
+
+    pub fn expire(ctx: Context<Expire>) -> Result<()> {
+        ctx.accounts.expire_and_close_vault()
+    }
+
+    pub fn make_basket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MakeBasket<'info>>,
+        seed: u64,
+        offered: Vec<BasketLeg>,
+        requested: Vec<BasketLeg>,
+    ) -> Result<()> {
+        ctx.accounts
+            .make_basket(seed, offered, requested, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn take_basket<'info>(ctx: Context<'_, '_, 'info, 'info, TakeBasket<'info>>) -> Result<()> {
+        ctx.accounts.take_basket(ctx.remaining_accounts)
+    }
+
+    pub fn refund_basket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBasket<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.refund_basket(ctx.remaining_accounts)
+    }
+
+    pub fn make_asset_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MakeAssetOffer<'info>>,
+        seed: u64,
+        offered: OfferLeg,
+        requested: OfferLeg,
+    ) -> Result<()> {
+        ctx.accounts
+            .make_asset_offer(seed, offered, requested, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn take_asset_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TakeAssetOffer<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.take_asset_offer(ctx.remaining_accounts)
+    }
+
+    pub fn refund_asset_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundAssetOffer<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.refund_asset_offer(ctx.remaining_accounts)
+    }
+
+    pub fn make_arbitrated(
+        ctx: Context<MakeArbitrated>,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+        arbiter: Pubkey,
+        timeout: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .make_arbitrated(seed, deposit, receive, arbiter, timeout, &ctx.bumps)
+    }
+
+    pub fn fund_arbitrated(ctx: Context<FundArbitrated>) -> Result<()> {
+        ctx.accounts.fund_arbitrated()
+    }
+
+    pub fn confirm_arbitrated(ctx: Context<UpdateArbitrated>) -> Result<()> {
+        ctx.accounts.confirm_arbitrated()
+    }
+
+    pub fn raise_dispute(ctx: Context<UpdateArbitrated>) -> Result<()> {
+        ctx.accounts.raise_dispute()
+    }
+
+    pub fn release_arbitrated(ctx: Context<SettleArbitrated>) -> Result<()> {
+        ctx.accounts.release_arbitrated()
+    }
+
+    pub fn resolve_dispute(ctx: Context<SettleArbitrated>, winner: Party) -> Result<()> {
+        ctx.accounts.resolve_dispute(winner)
+    }
+
+    pub fn refund_arbitrated(ctx: Context<RefundArbitrated>) -> Result<()> {
+        ctx.accounts.refund_arbitrated()
+    }
+
+    pub fn make_milestones(
+        ctx: Context<MakeMilestones>,
+        seed: u64,
+        payee: Pubkey,
+        milestones: Vec<Milestone>,
+    ) -> Result<()> {
+        ctx.accounts
+            .make_milestones(seed, payee, milestones, &ctx.bumps)
+    }
+
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+        ctx.accounts.approve_milestone(index)
+    }
+
+    pub fn release_milestone(ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+        ctx.accounts.release_milestone(index)
+    }
+
+    pub fn refund_milestones(ctx: Context<RefundMilestones>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault()
+    }
+
+    pub fn make_counter_offer(ctx: Context<MakeCounterOffer>, receive: u64) -> Result<()> {
+        ctx.accounts.make_counter_offer(receive, &ctx.bumps)
+    }
+
+    pub fn accept_counter_offer(ctx: Context<AcceptCounterOffer>) -> Result<()> {
+        ctx.accounts.accept_counter_offer()
+    }
+
+    pub fn reject_counter_offer(ctx: Context<CancelCounterOffer>) -> Result<()> {
+        ctx.accounts.reject_counter_offer()
+    }
+
+    pub fn withdraw_counter_offer(ctx: Context<CancelCounterOffer>) -> Result<()> {
+        ctx.accounts.withdraw_counter_offer()
     }
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ArbitratedStatus {
+    Open,     // Waiting for a taker to fund mint B
+    Funded,   // Both vaults are funded, waiting on confirmations or the timeout
+    Disputed, // Only the arbiter can settle it now, until the dispute deadline
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Party {
+    Maker,
+    Taker,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ArbitratedEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub taker: Option<Pubkey>, // Set once a taker funds their side
+    pub arbiter: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub deposit: u64,
+    pub receive: u64,
+    pub timeout: i64,    // Seconds after funding until the escrow can be released without confirmations
+    pub release_at: i64, // Set when the taker funds
+    pub status: ArbitratedStatus,
+    pub maker_confirmed: bool,
+    pub taker_confirmed: bool,
+    pub fee_bps: u16,    // Protocol fee at the time the escrow was made
+    pub bump: u8,
+}
+
+impl ArbitratedEscrow {
+    pub fn party(&self, key: &Pubkey) -> Option<Party> {
+        if *key == self.maker {
+            Some(Party::Maker)
+        } else if self.taker == Some(*key) {
+            Some(Party::Taker)
+        } else {
+            None
+        }
+    }
+
+    // Disputes have to be raised before release_at, so the arbiter gets at
+    // least another timeout to rule
+    pub fn dispute_deadline(&self) -> i64 {
+        self.release_at.saturating_add(self.timeout)
+    }
+
+    // Undisputed escrows release once both parties confirm, or on their
+    // own once the timeout has passed. A dispute the arbiter never rules on
+    // falls back to the same default release after the dispute deadline
+    pub fn can_release(&self, now: i64) -> bool {
+        match self.status {
+            ArbitratedStatus::Funded => {
+                (self.maker_confirmed && self.taker_confirmed) || now >= self.release_at
+            }
+            ArbitratedStatus::Disputed => now >= self.dispute_deadline(),
+            ArbitratedStatus::Open => false,
+        }
+    }
+}
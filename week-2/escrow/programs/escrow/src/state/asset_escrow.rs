@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum OfferLeg {
+    Token {
+        mint: Pubkey,
+        amount: u64,
+    },
+    Asset {
+        asset: Pubkey,
+        collection: Option<Pubkey>, // Core needs the collection to move assets that belong to one
+    },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AssetEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub offered: OfferLeg,   // Held by the escrow, in a vault or as the asset's owner
+    pub requested: OfferLeg, // Paid by the taker straight to the maker
+    pub fee_bps: u16,        // Protocol fee at the time the offer was made
+    pub bump: u8,
+}
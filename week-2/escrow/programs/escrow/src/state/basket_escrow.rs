@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_BASKET_LEGS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct BasketLeg {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BasketEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    #[max_len(MAX_BASKET_LEGS)]
+    pub offered: Vec<BasketLeg>,   // Deposited by the maker, one vault per mint
+    #[max_len(MAX_BASKET_LEGS)]
+    pub requested: Vec<BasketLeg>, // Paid by the taker straight to the maker
+    pub fee_bps: u16,              // Protocol fee at the time the basket was made
+    pub bump: u8,
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CounterOffer {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint_a: Pubkey, // The escrow PDA can be refunded and made again with other mints,
+    pub mint_b: Pubkey, // so accepting checks them against what the taker agreed to
+    pub deposit: u64, // Mint A left in the escrow when the counter was made, all of it is asked for
+    pub receive: u64, // Mint B held in the counter vault
+    pub bump: u8,
+}
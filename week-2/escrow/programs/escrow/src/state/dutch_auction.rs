@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::EscrowError;
+
+// Prices are for whatever is left of the deposit, they are scaled alongside
+// it on partial fills and when the maker changes the deposit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DutchAuction {
+    pub start_receive: u64, // Mint B asked up to start_at
+    pub floor_receive: u64, // Mint B asked from end_at on
+    pub start_at: i64,
+    pub end_at: i64,
+}
+
+impl DutchAuction {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.floor_receive > 0
+                && self.start_receive >= self.floor_receive
+                && self.end_at > self.start_at,
+            EscrowError::InvalidAuction
+        );
+
+        Ok(())
+    }
+
+    // Decays linearly from the start price to the floor, rounded up so the
+    // maker is never asked less than the curve
+    pub fn price_at(&self, now: i64) -> u64 {
+        if now <= self.start_at {
+            return self.start_receive;
+        }
+
+        if now >= self.end_at {
+            return self.floor_receive;
+        }
+
+        let elapsed = (now - self.start_at) as u128;
+        let duration = (self.end_at - self.start_at) as u128;
+        let decay = u128::from(self.start_receive - self.floor_receive) * elapsed / duration;
+
+        self.start_receive - decay as u64
+    }
+
+    pub fn scale(&mut self, remaining: u64, deposit: u64) -> Result<()> {
+        self.start_receive = scale_amount(self.start_receive, remaining, deposit)?;
+        self.floor_receive = scale_amount(self.floor_receive, remaining, deposit)?;
+
+        Ok(())
+    }
+}
+
+fn scale_amount(amount: u64, remaining: u64, deposit: u64) -> Result<u64> {
+    let scaled = (u128::from(amount) * u128::from(remaining)).div_ceil(u128::from(deposit));
+
+    u64::try_from(scaled).map_err(|_| error!(EscrowError::Overflow))
+}
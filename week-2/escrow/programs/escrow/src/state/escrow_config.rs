@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::EscrowError;
+
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+#[account]
+#[derive(InitSpace)]
+pub struct EscrowConfig {
+    pub admin: Pubkey,
+    pub fee_bps: u16,         // Fee locked into every new offer, taken from what the maker receives
+    pub treasury: Pubkey,     // Owner of the ATAs the fees are sent to
+    pub paused: bool,         // Stops new offers from being made
+    pub bump: u8,
+}
+
+// Offers keep the fee they were made with, so the admin can't change it under an open offer
+pub fn protocol_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = u128::from(amount) * u128::from(fee_bps) / u128::from(MAX_FEE_BPS);
+
+    u64::try_from(fee).map_err(|_| error!(EscrowError::Overflow))
+}
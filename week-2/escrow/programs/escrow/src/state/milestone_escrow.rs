@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_MILESTONES: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    pub unlock_at: Option<i64>, // Releasable from this timestamp even without approval
+    pub approved: bool,         // Releasable once the maker approves it
+    pub released: bool,
+}
+
+impl Milestone {
+    pub fn is_releasable(&self, now: i64) -> bool {
+        !self.released
+            && (self.approved || self.unlock_at.is_some_and(|unlock_at| now >= unlock_at))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub payee: Pubkey,
+    pub mint: Pubkey,
+    pub milestones: [Milestone; MAX_MILESTONES], // Only the first milestone_count are used
+    pub milestone_count: u8,
+    pub bump: u8,
+}
+
+impl MilestoneEscrow {
+    pub fn active(&self) -> &[Milestone] {
+        &self.milestones[..usize::from(self.milestone_count)]
+    }
+
+    // A timestamp unlock is a promise to the payee the maker can't take back alone
+    pub fn has_time_locked(&self) -> bool {
+        self.active()
+            .iter()
+            .any(|milestone| !milestone.released && milestone.unlock_at.is_some())
+    }
+}
@@ -1,5 +1,34 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::EscrowError;
+
+pub mod dutch_auction;
+pub use dutch_auction::*;
+
+pub mod transfer_fee_mode;
+pub use transfer_fee_mode::*;
+
+pub mod escrow_config;
+pub use escrow_config::*;
+
+pub mod basket_escrow;
+pub use basket_escrow::*;
+
+pub mod asset_escrow;
+pub use asset_escrow::*;
+
+pub mod arbitrated_escrow;
+pub use arbitrated_escrow::*;
+
+pub mod milestone_escrow;
+pub use milestone_escrow::*;
+
+pub mod offer_book;
+pub use offer_book::*;
+
+pub mod counter_offer;
+pub use counter_offer::*;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Escrow {
@@ -7,6 +36,53 @@ pub struct Escrow {
     pub maker: Pubkey,
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
-    pub receive: u64,
+    pub deposit: u64, // Amount of mint A still left in the vault, as the vault's real balance
+    pub receive: u64, // Amount of mint B asked for what is left of the deposit
+    pub expires_at: Option<i64>, // Offer can no longer be taken from this timestamp
+    pub allowed_taker: Option<Pubkey>, // If set, only this taker can fill the offer
+    pub auction: Option<DutchAuction>, // If set, `receive` follows the auction price
+    pub fee_mode: TransferFeeMode, // Whether the maker's deposit and `receive` are gross or net of transfer fees
+    pub book_page: u32, // Page of the mint pair's offer book the offer is listed on
+    pub fee_bps: u16, // Protocol fee at the time the offer was made
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Escrow {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn refresh_receive(&mut self, now: i64) {
+        if let Some(auction) = &self.auction {
+            self.receive = auction.price_at(now);
+        }
+    }
+
+    pub fn fill(&mut self, amount: u64, receive: u64) -> Result<()> {
+        let deposit = self.deposit;
+
+        self.deposit -= amount;
+        self.receive -= receive;
+
+        if let Some(auction) = &mut self.auction {
+            auction.scale(self.deposit, deposit)?;
+        }
+
+        Ok(())
+    }
+
+    // Mint B owed for `amount` of mint A, rounded up so partial fills never
+    // pay the maker less than the offered rate
+    pub fn receive_for(&self, amount: u64) -> Result<u64> {
+        require!(amount > 0 && amount <= self.deposit, EscrowError::InvalidAmount);
+
+        if amount == self.deposit {
+            return Ok(self.receive);
+        }
+
+        let receive = (u128::from(amount) * u128::from(self.receive))
+            .div_ceil(u128::from(self.deposit));
+
+        u64::try_from(receive).map_err(|_| error!(EscrowError::Overflow))
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::EscrowError;
+
+pub const OFFER_BOOK_PAGE_SIZE: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct BookEntry {
+    pub escrow: Pubkey,
+    pub deposit: u64, // Mint A left in the offer
+    pub receive: u64, // Mint B asked for it, as of the last change to the offer
+}
+
+// Tracks how many pages a mint pair's book has, so clients know which pages
+// to read and makers can only open the next one
+#[account]
+#[derive(InitSpace)]
+pub struct OfferBookHeader {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub page_count: u32,
+    pub bump: u8,
+}
+
+// One page of open offers for a mint pair, clients read every page and
+// sort by price themselves
+#[account]
+#[derive(InitSpace)]
+pub struct OfferBook {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub page: u32,
+    #[max_len(OFFER_BOOK_PAGE_SIZE)]
+    pub entries: Vec<BookEntry>,
+    pub bump: u8,
+}
+
+impl OfferBook {
+    pub fn insert(&mut self, escrow: Pubkey, deposit: u64, receive: u64) -> Result<()> {
+        require!(self.entries.len() < OFFER_BOOK_PAGE_SIZE, EscrowError::BookPageFull);
+
+        self.entries.push(BookEntry {
+            escrow,
+            deposit,
+            receive,
+        });
+        Ok(())
+    }
+
+    pub fn update(&mut self, escrow: &Pubkey, deposit: u64, receive: u64) -> Result<()> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.escrow == *escrow)
+            .ok_or(EscrowError::NotInBook)?;
+
+        entry.deposit = deposit;
+        entry.receive = receive;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, escrow: &Pubkey) -> Result<()> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.escrow == *escrow)
+            .ok_or(EscrowError::NotInBook)?;
+
+        self.entries.swap_remove(index);
+        Ok(())
+    }
+}
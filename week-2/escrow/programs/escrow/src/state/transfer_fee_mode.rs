@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+// How amounts are read for mints that charge a Token-2022 transfer fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum TransferFeeMode {
+    Gross, // Amounts are what is sent, the recipient gets them less the fee
+    Net,   // Amounts are what arrives, the sender pays the fee on top
+}
@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::{
+        create, create_idempotent, get_associated_token_address_with_program_id, Create,
+    },
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_2022_extensions::transfer_fee::{
+        harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint,
+    },
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TransferChecked,
+    },
+};
+
+use mpl_core::{instructions::TransferV1CpiBuilder, ID as CORE_PROGRAM_ID};
+
+use crate::{errors::EscrowError, state::TransferFeeMode};
+
+// Helpers for instructions that take their token accounts through
+// remaining accounts, where anchor's account constraints can't be used
+
+pub fn load_mint<'info>(
+    info: &'info AccountInfo<'info>,
+    expected: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<InterfaceAccount<'info, Mint>> {
+    require_keys_eq!(*info.key, *expected, EscrowError::InvalidMint);
+    require_keys_eq!(*info.owner, *token_program, EscrowError::InvalidMint);
+
+    InterfaceAccount::<Mint>::try_from(info)
+}
+
+// Baskets can mix token programs, every leg goes through the program that owns its mint
+pub fn token_program_for<'info>(
+    mint: &AccountInfo,
+    token_program: &Program<'info, Token>,
+    token_2022_program: &Program<'info, Token2022>,
+) -> Result<AccountInfo<'info>> {
+    if *mint.owner == token_program.key() {
+        Ok(token_program.to_account_info())
+    } else if *mint.owner == token_2022_program.key() {
+        Ok(token_2022_program.to_account_info())
+    } else {
+        err!(EscrowError::InvalidMint)
+    }
+}
+
+pub fn check_ata(
+    info: &AccountInfo,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        *info.key,
+        get_associated_token_address_with_program_id(authority, mint, token_program),
+        EscrowError::InvalidTokenAccount
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_ata<'info>(
+    payer: AccountInfo<'info>,
+    ata: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    associated_token_program: AccountInfo<'info>,
+    idempotent: bool,
+) -> Result<()> {
+    let create_accounts = Create {
+        payer,
+        associated_token: ata,
+        authority,
+        mint,
+        system_program,
+        token_program,
+    };
+
+    let create_ctx = CpiContext::new(associated_token_program, create_accounts);
+
+    if idempotent {
+        create_idempotent(create_ctx)
+    } else {
+        create(create_ctx)
+    }
+}
+
+pub fn transfer_tokens<'info>(
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let transfer_accounts = TransferChecked {
+        from,
+        mint: mint.to_account_info(),
+        to,
+        authority,
+    };
+
+    let transfer_ctx = CpiContext::new_with_signer(token_program, transfer_accounts, signer_seeds);
+
+    transfer_checked(transfer_ctx, amount, mint.decimals)
+}
+
+// Token-2022 won't close an account that still holds withheld transfer
+// fees, so they are moved to the mint first. Harvesting is permissionless,
+// the mint just has to be writable
+pub fn harvest_withheld_fees<'info>(
+    account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    if *token_program.key != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let withheld = {
+        let data = account.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+
+        state
+            .get_extension::<TransferFeeAmount>()
+            .map(|fee_amount| u64::from(fee_amount.withheld_amount))
+            .unwrap_or(0)
+    };
+
+    if withheld == 0 {
+        return Ok(());
+    }
+
+    let harvest_accounts = HarvestWithheldTokensToMint {
+        token_program_id: token_program.clone(),
+        mint,
+    };
+
+    let harvest_ctx = CpiContext::new(token_program, harvest_accounts);
+
+    harvest_withheld_tokens_to_mint(harvest_ctx, vec![account])
+}
+
+pub fn close_token_account<'info>(
+    account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    harvest_withheld_fees(account.clone(), mint, token_program.clone())?;
+
+    let close_accounts = CloseAccount {
+        account,
+        destination,
+        authority,
+    };
+
+    let close_ctx = CpiContext::new_with_signer(token_program, close_accounts, signer_seeds);
+
+    close_account(close_ctx)
+}
+
+// Core assets are checked against the offer here, the core program checks
+// ownership and the collection on transfer. Assets without a collection
+// take the core program in the collection slot
+pub fn check_asset(
+    asset_info: &AccountInfo,
+    collection_info: &AccountInfo,
+    asset: &Pubkey,
+    collection: &Option<Pubkey>,
+) -> Result<()> {
+    require_keys_eq!(*asset_info.key, *asset, EscrowError::InvalidAsset);
+    require_keys_eq!(*asset_info.owner, CORE_PROGRAM_ID, EscrowError::InvalidAsset);
+    require_keys_eq!(
+        *collection_info.key,
+        collection.unwrap_or(CORE_PROGRAM_ID),
+        EscrowError::InvalidAsset
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_asset<'info>(
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    payer: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    new_owner: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    TransferV1CpiBuilder::new(core_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(authority))
+        .new_owner(new_owner)
+        .system_program(Some(system_program))
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+// Only Token-2022 mints can carry the transfer fee extension
+fn transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+// What has to be sent for `amount` to be moved under the given mode, for
+// net amounts that is `amount` plus this epoch's transfer fee
+pub fn amount_to_send(mint: &AccountInfo, amount: u64, mode: TransferFeeMode) -> Result<u64> {
+    if mode == TransferFeeMode::Gross || amount == 0 {
+        return Ok(amount);
+    }
+
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(amount);
+    };
+
+    let fee = config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(EscrowError::Overflow)?;
+
+    amount.checked_add(fee).ok_or(error!(EscrowError::Overflow))
+}
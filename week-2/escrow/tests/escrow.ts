@@ -2,7 +2,11 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Escrow } from "../target/types/escrow";
 import { expect } from "chai";
-import { getAssociatedTokenAddressSync, createAssociatedTokenAccountInstruction, createMint, mintTo, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { getAssociatedTokenAddressSync, createAssociatedTokenAccountInstruction, createMint, mintTo, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ExtensionType, getMintLen, createInitializeTransferFeeConfigInstruction, createInitializeMintInstruction, getMint, getTransferFeeConfig } from "@solana/spl-token";
+import { create, fetchAssetV1, mplCore, MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+import { generateSigner, keypairIdentity } from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import { fromWeb3JsKeypair, toWeb3JsPublicKey } from "@metaplex-foundation/umi-web3js-adapters";
 
 describe("escrow", () => {
   const provider = anchor.AnchorProvider.env();
@@ -25,6 +29,18 @@ describe("escrow", () => {
   let escrowBump: number;
   let vault: anchor.web3.PublicKey;
 
+  const treasury = anchor.web3.Keypair.generate().publicKey;
+  let treasuryAtaB: anchor.web3.PublicKey;
+
+  const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+
+  // Every offer in these tests fits on the first page of the book
+  let bookHeaderPda: anchor.web3.PublicKey;
+  let bookPda: anchor.web3.PublicKey;
+
   const depositAmount = 100;
   const receiveAmount = 200;
 
@@ -44,14 +60,43 @@ describe("escrow", () => {
       createAssociatedTokenAccountInstruction(provider.wallet.publicKey, makerAtaA, maker, mintA)
     );
     await provider.sendAndConfirm(makerAtaATx);
-    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, depositAmount * 2);
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, depositAmount * 10);
 
     takerAtaB = getAssociatedTokenAddressSync(mintB, taker.publicKey);
     const takerAtaBTx = new anchor.web3.Transaction().add(
       createAssociatedTokenAccountInstruction(taker.publicKey, takerAtaB, taker.publicKey, mintB)
     );
     await provider.sendAndConfirm(takerAtaBTx, [taker]);
-    await mintTo(provider.connection, taker, mintB, takerAtaB, taker, receiveAmount * 2);
+    await mintTo(provider.connection, taker, mintB, takerAtaB, taker, receiveAmount * 10);
+
+    treasuryAtaB = getAssociatedTokenAddressSync(mintB, treasury);
+
+    [bookHeaderPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book_header"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId
+    );
+    [bookPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book"), mintA.toBuffer(), mintB.toBuffer(), Buffer.alloc(4)],
+      program.programId
+    );
+
+    // The test validator deploys the program with the wallet as upgrade authority
+    const [programDataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    // No fee until the fee test turns it on
+    await program.methods
+      .initializeConfig(0, treasury)
+      .accountsStrict({
+        admin: maker,
+        config: configPda,
+        program: program.programId,
+        programData: programDataPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
 
   });
 
@@ -65,14 +110,17 @@ describe("escrow", () => {
 
     // Make
     await program.methods
-      .make(seed1, new anchor.BN(depositAmount), new anchor.BN(receiveAmount))
+      .make(seed1, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
       .accountsStrict({
         maker: maker,
+        config: configPda,
         mintA: mintA,
         mintB: mintB,
         makerAtaA: makerAtaA,
         escrow: escrowPda,
         vault: vault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -83,6 +131,7 @@ describe("escrow", () => {
     expect(escrowAccount.maker.toBase58()).to.equal(maker.toBase58());
     expect(escrowAccount.mintA.toBase58()).to.equal(mintA.toBase58());
     expect(escrowAccount.mintB.toBase58()).to.equal(mintB.toBase58());
+    expect(escrowAccount.deposit.toNumber()).to.equal(depositAmount);
     expect(escrowAccount.receive.toNumber()).to.equal(receiveAmount);
     expect(escrowAccount.bump).to.equal(escrowBump);
 
@@ -98,6 +147,7 @@ describe("escrow", () => {
         makerAtaA: makerAtaA,
         escrow: escrowPda,
         vault: vault,
+        book: bookPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -122,14 +172,17 @@ describe("escrow", () => {
 
     // Make (again for take path)
     await program.methods
-      .make(seed2, new anchor.BN(depositAmount), new anchor.BN(receiveAmount))
+      .make(seed2, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
       .accountsStrict({
         maker: maker,
+        config: configPda,
         mintA: mintA,
         mintB: mintB,
         makerAtaA: makerAtaA,
         escrow: escrowPda,
         vault: vault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -142,7 +195,7 @@ describe("escrow", () => {
 
     // Take
     await program.methods
-      .take()
+      .take(new anchor.BN(depositAmount))
       .accountsStrict({
         taker: taker.publicKey,
         maker: maker,
@@ -151,8 +204,12 @@ describe("escrow", () => {
         takerAtaA: takerAtaA,
         takerAtaB: takerAtaB,
         makerAtaB: makerAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: treasuryAtaB,
         escrow: escrowPda,
         vault: vault,
+        book: bookPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -173,5 +230,1586 @@ describe("escrow", () => {
 
     const makerBalanceB = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
     expect(makerBalanceB).to.equal(receiveAmount);
+
+    // Without a fee the taker doesn't pay for the treasury's token account
+    expect(await provider.connection.getAccountInfo(treasuryAtaB)).to.be.null;
+  });
+  it("Partially fills the escrow", async () => {
+    const seed3 = new anchor.BN(3333);
+    [escrowPda, escrowBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), seed3.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mintA, escrowPda, true);
+
+    await program.methods
+      .make(seed3, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: escrowPda,
+        vault: vault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const takeAccounts = {
+      taker: taker.publicKey,
+      maker: maker,
+      mintA: mintA,
+      mintB: mintB,
+      takerAtaA: takerAtaA,
+      takerAtaB: takerAtaB,
+      makerAtaB: makerAtaB,
+      config: configPda,
+      treasury: treasury,
+      treasuryAtaB: treasuryAtaB,
+      escrow: escrowPda,
+      vault: vault,
+      book: bookPda,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    const takerBalanceABefore = (await provider.connection.getTokenAccountBalance(takerAtaA)).value.uiAmount;
+    const makerBalanceBBefore = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+
+    // Fill a quarter of the offer
+    await program.methods
+      .take(new anchor.BN(depositAmount / 4))
+      .accountsStrict(takeAccounts)
+      .signers([taker])
+      .rpc();
+
+    const escrowAccount = await program.account.escrow.fetch(escrowPda);
+    expect(escrowAccount.deposit.toNumber()).to.equal(depositAmount * 3 / 4);
+    expect(escrowAccount.receive.toNumber()).to.equal(receiveAmount * 3 / 4);
+
+    // The book follows the partial fill
+    let book = await program.account.offerBook.fetch(bookPda);
+    const entry = book.entries.find(entry => entry.escrow.equals(escrowPda));
+    expect(entry.deposit.toNumber()).to.equal(depositAmount * 3 / 4);
+    expect(entry.receive.toNumber()).to.equal(receiveAmount * 3 / 4);
+
+    // A stray unit sent to the vault must not stop the last fill from closing it
+    await mintTo(provider.connection, provider.wallet.payer, mintA, vault, provider.wallet.payer, 1);
+
+    // Fill the rest, which sweeps the vault and closes the escrow
+    await program.methods
+      .take(new anchor.BN(depositAmount * 3 / 4))
+      .accountsStrict(takeAccounts)
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(escrowPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+
+    book = await program.account.offerBook.fetch(bookPda);
+    expect(book.entries.some(entry => entry.escrow.equals(escrowPda))).to.be.false;
+
+    const takerBalanceA = (await provider.connection.getTokenAccountBalance(takerAtaA)).value.uiAmount;
+    expect(takerBalanceA).to.equal(takerBalanceABefore + depositAmount + 1);
+
+    const makerBalanceB = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+    expect(makerBalanceB).to.equal(makerBalanceBBefore + receiveAmount);
+  });
+  it("Expires the escrow permissionlessly", async () => {
+    const seed4 = new anchor.BN(4444);
+    [escrowPda, escrowBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), seed4.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mintA, escrowPda, true);
+
+    const expiresAt = Math.floor(Date.now() / 1000) + 2;
+
+    await program.methods
+      .make(seed4, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), new anchor.BN(expiresAt), null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: escrowPda,
+        vault: vault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const makerBalanceABefore = (await provider.connection.getTokenAccountBalance(makerAtaA)).value.uiAmount;
+
+    await new Promise(resolve => setTimeout(resolve, 4000));
+
+    try {
+      await program.methods
+        .take(new anchor.BN(depositAmount))
+        .accountsStrict({
+          taker: taker.publicKey,
+          maker: maker,
+          mintA: mintA,
+          mintB: mintB,
+          takerAtaA: takerAtaA,
+          takerAtaB: takerAtaB,
+          makerAtaB: makerAtaB,
+          config: configPda,
+          treasury: treasury,
+          treasuryAtaB: treasuryAtaB,
+          escrow: escrowPda,
+          vault: vault,
+          book: bookPda,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+      expect.fail("Taking an expired offer should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OfferExpired");
+    }
+
+    // The taker cleans up the expired offer on the maker's behalf
+    await program.methods
+      .expire()
+      .accountsStrict({
+        caller: taker.publicKey,
+        maker: maker,
+        mintA: mintA,
+        makerAtaA: makerAtaA,
+        escrow: escrowPda,
+        vault: vault,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(escrowPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+
+    const makerBalanceA = (await provider.connection.getTokenAccountBalance(makerAtaA)).value.uiAmount;
+    expect(makerBalanceA).to.equal(makerBalanceABefore + depositAmount);
+  });
+  it("Rejects takers other than the designated one", async () => {
+    const seed5 = new anchor.BN(5555);
+    [escrowPda, escrowBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), seed5.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mintA, escrowPda, true);
+
+    const allowedTaker = anchor.web3.Keypair.generate().publicKey;
+
+    await program.methods
+      .make(seed5, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, allowedTaker, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: escrowPda,
+        vault: vault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const escrowAccount = await program.account.escrow.fetch(escrowPda);
+    expect(escrowAccount.allowedTaker.toBase58()).to.equal(allowedTaker.toBase58());
+
+    try {
+      await program.methods
+        .take(new anchor.BN(depositAmount))
+        .accountsStrict({
+          taker: taker.publicKey,
+          maker: maker,
+          mintA: mintA,
+          mintB: mintB,
+          takerAtaA: takerAtaA,
+          takerAtaB: takerAtaB,
+          makerAtaB: makerAtaB,
+          config: configPda,
+          treasury: treasury,
+          treasuryAtaB: treasuryAtaB,
+          escrow: escrowPda,
+          vault: vault,
+          book: bookPda,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+      expect.fail("Only the designated taker should be able to take");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TakerNotAllowed");
+    }
+
+    await program.methods
+      .refund()
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        makerAtaA: makerAtaA,
+        escrow: escrowPda,
+        vault: vault,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  });
+  it("Routes the protocol fee to the treasury", async () => {
+    const seed6 = new anchor.BN(6666);
+    [escrowPda, escrowBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), seed6.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mintA, escrowPda, true);
+
+    // 1%
+    await program.methods
+      .updateFee(100)
+      .accountsStrict({
+        admin: maker,
+        config: configPda,
+      })
+      .rpc();
+
+    await program.methods
+      .make(seed6, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: escrowPda,
+        vault: vault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // The offer keeps the 1% it was made with even if the fee goes up before the take
+    await program.methods
+      .updateFee(10_000)
+      .accountsStrict({
+        admin: maker,
+        config: configPda,
+      })
+      .rpc();
+
+    const makerBalanceBBefore = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+
+    await program.methods
+      .take(new anchor.BN(depositAmount))
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker,
+        mintA: mintA,
+        mintB: mintB,
+        takerAtaA: takerAtaA,
+        takerAtaB: takerAtaB,
+        makerAtaB: makerAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: treasuryAtaB,
+        escrow: escrowPda,
+        vault: vault,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    const fee = receiveAmount / 100;
+
+    const treasuryBalanceB = (await provider.connection.getTokenAccountBalance(treasuryAtaB)).value.uiAmount;
+    expect(treasuryBalanceB).to.equal(fee);
+
+    const makerBalanceB = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+    expect(makerBalanceB).to.equal(makerBalanceBBefore + receiveAmount - fee);
+
+    await program.methods
+      .updateFee(0)
+      .accountsStrict({
+        admin: maker,
+        config: configPda,
+      })
+      .rpc();
+  });
+
+  it("Rejects new offers while paused", async () => {
+    const seed7 = new anchor.BN(7777);
+    [escrowPda, escrowBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), seed7.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mintA, escrowPda, true);
+
+    await program.methods
+      .setPaused(true)
+      .accountsStrict({
+        admin: maker,
+        config: configPda,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .make(seed7, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+        .accountsStrict({
+          maker: maker,
+          config: configPda,
+          mintA: mintA,
+          mintB: mintB,
+          makerAtaA: makerAtaA,
+          escrow: escrowPda,
+          vault: vault,
+          bookHeader: bookHeaderPda,
+          book: bookPda,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Making an offer while paused should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Paused");
+    }
+
+    await program.methods
+      .setPaused(false)
+      .accountsStrict({
+        admin: maker,
+        config: configPda,
+      })
+      .rpc();
+  });
+  it("Makes and takes a basket escrow", async () => {
+    const basketSeed = new anchor.BN(8888);
+    const [basketPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("basket"), maker.toBuffer(), basketSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Second offered mint, under Token-2022 to mix token programs in one basket
+    const mintC = await createMint(provider.connection, provider.wallet.payer, maker, null, 0, undefined, undefined, TOKEN_2022_PROGRAM_ID);
+    const makerAtaC = getAssociatedTokenAddressSync(mintC, maker, false, TOKEN_2022_PROGRAM_ID);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(maker, makerAtaC, maker, mintC, TOKEN_2022_PROGRAM_ID)
+    ));
+    await mintTo(provider.connection, provider.wallet.payer, mintC, makerAtaC, provider.wallet.payer, 50, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    const vaultA = getAssociatedTokenAddressSync(mintA, basketPda, true);
+    const vaultC = getAssociatedTokenAddressSync(mintC, basketPda, true, TOKEN_2022_PROGRAM_ID);
+    const takerAtaC = getAssociatedTokenAddressSync(mintC, taker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+
+    const offered = [
+      { mint: mintA, amount: new anchor.BN(10) },
+      { mint: mintC, amount: new anchor.BN(50) },
+    ];
+    const requested = [{ mint: mintB, amount: new anchor.BN(30) }];
+
+    const writable = (pubkey: anchor.web3.PublicKey) => ({ pubkey, isSigner: false, isWritable: true });
+    const readonly = (pubkey: anchor.web3.PublicKey) => ({ pubkey, isSigner: false, isWritable: false });
+
+    await program.methods
+      .makeBasket(basketSeed, offered, requested)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        basket: basketPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        readonly(mintA), writable(makerAtaA), writable(vaultA),
+        readonly(mintC), writable(makerAtaC), writable(vaultC),
+      ])
+      .rpc();
+
+    expect((await provider.connection.getTokenAccountBalance(vaultC)).value.uiAmount).to.equal(50);
+
+    const takerBalanceABefore = (await provider.connection.getTokenAccountBalance(takerAtaA)).value.uiAmount;
+    const makerBalanceBBefore = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+
+    await program.methods
+      .takeBasket()
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker,
+        config: configPda,
+        treasury: treasury,
+        basket: basketPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        writable(mintA), writable(vaultA), writable(takerAtaA),
+        writable(mintC), writable(vaultC), writable(takerAtaC),
+        readonly(mintB), writable(takerAtaB), writable(makerAtaB), writable(treasuryAtaB),
+      ])
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(basketPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(vaultA)).to.be.null;
+    expect(await provider.connection.getAccountInfo(vaultC)).to.be.null;
+
+    expect((await provider.connection.getTokenAccountBalance(takerAtaA)).value.uiAmount).to.equal(takerBalanceABefore + 10);
+    expect((await provider.connection.getTokenAccountBalance(takerAtaC)).value.uiAmount).to.equal(50);
+    expect((await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount).to.equal(makerBalanceBBefore + 30);
+  });
+  it("Swaps a core asset for tokens", async () => {
+    const umi = createUmi(provider.connection.rpcEndpoint, "confirmed")
+      .use(mplCore())
+      .use(keypairIdentity(fromWeb3JsKeypair(provider.wallet.payer)));
+    const coreProgram = toWeb3JsPublicKey(MPL_CORE_PROGRAM_ID);
+
+    // No collection, owned and updated by the maker
+    const assetSigner = generateSigner(umi);
+    await create(umi, {
+      asset: assetSigner,
+      name: "Escrowed Asset",
+      uri: "https://example.com/asset.json",
+    }).sendAndConfirm(umi);
+    const asset = toWeb3JsPublicKey(assetSigner.publicKey);
+
+    const assetOwner = async () =>
+      toWeb3JsPublicKey((await fetchAssetV1(umi, assetSigner.publicKey)).owner);
+
+    const assetSeed = new anchor.BN(9999);
+    const [assetEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("asset_escrow"), maker.toBuffer(), assetSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const writable = (pubkey: anchor.web3.PublicKey) => ({ pubkey, isSigner: false, isWritable: true });
+    const readonly = (pubkey: anchor.web3.PublicKey) => ({ pubkey, isSigner: false, isWritable: false });
+
+    await program.methods
+      .makeAssetOffer(
+        assetSeed,
+        { asset: { asset, collection: null } },
+        { token: { mint: mintB, amount: new anchor.BN(25) } }
+      )
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        escrow: assetEscrowPda,
+        coreProgram: coreProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([writable(asset), readonly(coreProgram)])
+      .rpc();
+
+    expect((await assetOwner()).toBase58()).to.equal(assetEscrowPda.toBase58());
+
+    const makerBalanceBBefore = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+
+    await program.methods
+      .takeAssetOffer()
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker,
+        config: configPda,
+        treasury: treasury,
+        escrow: assetEscrowPda,
+        coreProgram: coreProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        writable(asset), readonly(coreProgram),
+        readonly(mintB), writable(takerAtaB), writable(makerAtaB), writable(treasuryAtaB),
+      ])
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(assetEscrowPda)).to.be.null;
+    expect((await assetOwner()).toBase58()).to.equal(taker.publicKey.toBase58());
+    expect((await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount).to.equal(makerBalanceBBefore + 25);
+  });
+  it("Updates an open offer in place", async () => {
+    const updateSeed = new anchor.BN(1010);
+    const [updateEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), updateSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const updateVault = getAssociatedTokenAddressSync(mintA, updateEscrowPda, true);
+
+    await program.methods
+      .make(updateSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: updateEscrowPda,
+        vault: updateVault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const updateAccounts = {
+      maker: maker,
+      mintA: mintA,
+      makerAtaA: makerAtaA,
+      escrow: updateEscrowPda,
+      vault: updateVault,
+      book: bookPda,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Top up the deposit and raise the price
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await program.methods
+      .updateOffer(new anchor.BN(depositAmount + 50), new anchor.BN(receiveAmount * 2), expiresAt)
+      .accountsStrict(updateAccounts)
+      .rpc();
+
+    let escrowAccount = await program.account.escrow.fetch(updateEscrowPda);
+    expect(escrowAccount.deposit.toNumber()).to.equal(depositAmount + 50);
+    expect(escrowAccount.receive.toNumber()).to.equal(receiveAmount * 2);
+    expect(escrowAccount.expiresAt.toNumber()).to.equal(expiresAt.toNumber());
+    expect((await provider.connection.getTokenAccountBalance(updateVault)).value.uiAmount).to.equal(depositAmount + 50);
+
+    // Withdraw part of it, leaving everything else as is
+    await program.methods
+      .updateOffer(new anchor.BN(depositAmount / 2), null, null)
+      .accountsStrict(updateAccounts)
+      .rpc();
+
+    escrowAccount = await program.account.escrow.fetch(updateEscrowPda);
+    expect(escrowAccount.deposit.toNumber()).to.equal(depositAmount / 2);
+    expect(escrowAccount.receive.toNumber()).to.equal(receiveAmount * 2);
+    expect((await provider.connection.getTokenAccountBalance(updateVault)).value.uiAmount).to.equal(depositAmount / 2);
+
+    await program.methods
+      .refund()
+      .accountsStrict(updateAccounts)
+      .rpc();
+  });
+  it("Sells through a dutch auction", async () => {
+    const auctionSeed = new anchor.BN(1212);
+    const [auctionEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), auctionSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const auctionVault = getAssociatedTokenAddressSync(mintA, auctionEscrowPda, true);
+
+    // Halfway through an auction from the full price down to half of it
+    const now = Math.floor(Date.now() / 1000);
+    const auction = {
+      startReceive: new anchor.BN(receiveAmount),
+      floorReceive: new anchor.BN(receiveAmount / 2),
+      startAt: new anchor.BN(now - 1000),
+      endAt: new anchor.BN(now + 1000),
+    };
+
+    await program.methods
+      .make(auctionSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, auction, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: auctionEscrowPda,
+        vault: auctionVault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    let escrowAccount = await program.account.escrow.fetch(auctionEscrowPda);
+    expect(escrowAccount.receive.toNumber()).to.be.lessThan(receiveAmount);
+    expect(escrowAccount.receive.toNumber()).to.be.greaterThan(receiveAmount / 2);
+
+    // Doubling the deposit doubles the whole curve, and the book shows the new price
+    await program.methods
+      .updateOffer(new anchor.BN(depositAmount * 2), null, null)
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        makerAtaA: makerAtaA,
+        escrow: auctionEscrowPda,
+        vault: auctionVault,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    escrowAccount = await program.account.escrow.fetch(auctionEscrowPda);
+    expect(escrowAccount.auction.startReceive.toNumber()).to.equal(receiveAmount * 2);
+    expect(escrowAccount.auction.floorReceive.toNumber()).to.equal(receiveAmount);
+    expect(escrowAccount.receive.toNumber()).to.be.lessThan(receiveAmount * 2);
+    expect(escrowAccount.receive.toNumber()).to.be.greaterThan(receiveAmount);
+
+    const entry = (await program.account.offerBook.fetch(bookPda)).entries
+      .find(entry => entry.escrow.equals(auctionEscrowPda));
+    expect(entry.deposit.toNumber()).to.equal(depositAmount * 2);
+    expect(entry.receive.toNumber()).to.equal(escrowAccount.receive.toNumber());
+
+    const makerBalanceBBefore = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+
+    await program.methods
+      .take(new anchor.BN(depositAmount * 2))
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker,
+        mintA: mintA,
+        mintB: mintB,
+        takerAtaA: takerAtaA,
+        takerAtaB: takerAtaB,
+        makerAtaB: makerAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: treasuryAtaB,
+        escrow: auctionEscrowPda,
+        vault: auctionVault,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(auctionEscrowPda)).to.be.null;
+
+    const paid = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount - makerBalanceBBefore;
+    expect(paid).to.be.lessThan(receiveAmount * 2);
+    expect(paid).to.be.greaterThan(receiveAmount);
+  });
+  it("Settles arbitrated escrows by confirmation and by ruling", async () => {
+    const arbiter = anchor.web3.Keypair.generate();
+    await provider.connection.requestAirdrop(arbiter.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const makeAndFund = async (arbitratedSeed: anchor.BN, timeout = 3600) => {
+      const [arbitratedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("arbitrated"), maker.toBuffer(), arbitratedSeed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const vaultA = getAssociatedTokenAddressSync(mintA, arbitratedPda, true);
+      const vaultB = getAssociatedTokenAddressSync(mintB, arbitratedPda, true);
+
+      await program.methods
+        .makeArbitrated(arbitratedSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), arbiter.publicKey, new anchor.BN(timeout))
+        .accountsStrict({
+          maker: maker,
+          config: configPda,
+          mintA: mintA,
+          mintB: mintB,
+          makerAtaA: makerAtaA,
+          escrow: arbitratedPda,
+          vaultA: vaultA,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .fundArbitrated()
+        .accountsStrict({
+          taker: taker.publicKey,
+          mintB: mintB,
+          takerAtaB: takerAtaB,
+          escrow: arbitratedPda,
+          vaultB: vaultB,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+
+      const settleAccounts = {
+        maker: maker,
+        taker: taker.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        makerAtaB: makerAtaB,
+        takerAtaA: takerAtaA,
+        takerAtaB: takerAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: treasuryAtaB,
+        escrow: arbitratedPda,
+        vaultA: vaultA,
+        vaultB: vaultB,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+
+      return { arbitratedPda, settleAccounts };
+    };
+
+    const balance = async (ata: anchor.web3.PublicKey) =>
+      (await provider.connection.getTokenAccountBalance(ata)).value.uiAmount;
+
+    // Both parties confirm delivery, the swap goes through
+    const confirmed = await makeAndFund(new anchor.BN(1313));
+
+    const takerBalanceABefore = await balance(takerAtaA);
+    const makerBalanceBBefore = await balance(makerAtaB);
+
+    await program.methods
+      .confirmArbitrated()
+      .accountsStrict({ party: maker, escrow: confirmed.arbitratedPda })
+      .rpc();
+
+    try {
+      await program.methods
+        .releaseArbitrated()
+        .accountsStrict({ caller: maker, ...confirmed.settleAccounts })
+        .rpc();
+      expect.fail("Release should wait for the taker's confirmation");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotReleasable");
+    }
+
+    await program.methods
+      .confirmArbitrated()
+      .accountsStrict({ party: taker.publicKey, escrow: confirmed.arbitratedPda })
+      .signers([taker])
+      .rpc();
+
+    await program.methods
+      .releaseArbitrated()
+      .accountsStrict({ caller: maker, ...confirmed.settleAccounts })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(confirmed.arbitratedPda)).to.be.null;
+    expect(await balance(takerAtaA)).to.equal(takerBalanceABefore + depositAmount);
+    expect(await balance(makerAtaB)).to.equal(makerBalanceBBefore + receiveAmount);
+
+    // The maker disputes and the arbiter unwinds the escrow
+    const disputed = await makeAndFund(new anchor.BN(1414));
+
+    const makerBalanceABefore = await balance(makerAtaA);
+    const takerBalanceBBefore = await balance(takerAtaB);
+
+    await program.methods
+      .raiseDispute()
+      .accountsStrict({ party: maker, escrow: disputed.arbitratedPda })
+      .rpc();
+
+    const escrowAccount = await program.account.arbitratedEscrow.fetch(disputed.arbitratedPda);
+    expect(escrowAccount.status).to.deep.equal({ disputed: {} });
+
+    await program.methods
+      .resolveDispute({ maker: {} })
+      .accountsStrict({ caller: arbiter.publicKey, ...disputed.settleAccounts })
+      .signers([arbiter])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(disputed.arbitratedPda)).to.be.null;
+    expect(await balance(makerAtaA)).to.equal(makerBalanceABefore + depositAmount);
+    expect(await balance(takerAtaB)).to.equal(takerBalanceBBefore + receiveAmount);
+
+    // The arbiter never rules, so the taker releases once the dispute deadline passes
+    const abandoned = await makeAndFund(new anchor.BN(1919), 2);
+
+    await program.methods
+      .raiseDispute()
+      .accountsStrict({ party: maker, escrow: abandoned.arbitratedPda })
+      .rpc();
+
+    try {
+      await program.methods
+        .releaseArbitrated()
+        .accountsStrict({ caller: taker.publicKey, ...abandoned.settleAccounts })
+        .signers([taker])
+        .rpc();
+      expect.fail("Release should wait for the dispute deadline");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotReleasable");
+    }
+
+    await new Promise(resolve => setTimeout(resolve, 5000));
+
+    const takerBalanceABefore2 = await balance(takerAtaA);
+
+    await program.methods
+      .releaseArbitrated()
+      .accountsStrict({ caller: taker.publicKey, ...abandoned.settleAccounts })
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(abandoned.arbitratedPda)).to.be.null;
+    expect(await balance(takerAtaA)).to.equal(takerBalanceABefore2 + depositAmount);
+
+    // The maker can't name themselves as the arbiter
+    const [selfArbitratedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("arbitrated"), maker.toBuffer(), new anchor.BN(2020).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .makeArbitrated(new anchor.BN(2020), new anchor.BN(depositAmount), new anchor.BN(receiveAmount), maker, new anchor.BN(3600))
+        .accountsStrict({
+          maker: maker,
+          config: configPda,
+          mintA: mintA,
+          mintB: mintB,
+          makerAtaA: makerAtaA,
+          escrow: selfArbitratedPda,
+          vaultA: getAssociatedTokenAddressSync(mintA, selfArbitratedPda, true),
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("The maker shouldn't be able to arbitrate their own escrow");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidArbiter");
+    }
+  });
+  it("Funds arbitrated escrows into a pre-created vault but never by the arbiter", async () => {
+    const arbiter = anchor.web3.Keypair.generate().publicKey;
+
+    const arbitratedAccounts = (arbitratedSeed: anchor.BN) => {
+      const [arbitratedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("arbitrated"), maker.toBuffer(), arbitratedSeed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      return {
+        arbitratedPda,
+        vaultA: getAssociatedTokenAddressSync(mintA, arbitratedPda, true),
+        vaultB: getAssociatedTokenAddressSync(mintB, arbitratedPda, true),
+      };
+    };
+
+    const makeArbitrated = (arbitratedSeed: anchor.BN, arbiterKey: anchor.web3.PublicKey, offeredMintB: anchor.web3.PublicKey) => {
+      const { arbitratedPda, vaultA } = arbitratedAccounts(arbitratedSeed);
+      return program.methods
+        .makeArbitrated(arbitratedSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), arbiterKey, new anchor.BN(3600))
+        .accountsStrict({
+          maker: maker,
+          config: configPda,
+          mintA: mintA,
+          mintB: offeredMintB,
+          makerAtaA: makerAtaA,
+          escrow: arbitratedPda,
+          vaultA: vaultA,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    };
+
+    const fundArbitrated = (arbitratedSeed: anchor.BN) => {
+      const { arbitratedPda, vaultB } = arbitratedAccounts(arbitratedSeed);
+      return program.methods
+        .fundArbitrated()
+        .accountsStrict({
+          taker: taker.publicKey,
+          mintB: mintB,
+          takerAtaB: takerAtaB,
+          escrow: arbitratedPda,
+          vaultB: vaultB,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+    };
+
+    // Both legs in one mint would share a single vault
+    try {
+      await makeArbitrated(new anchor.BN(2121), arbiter, mintA);
+      expect.fail("Offering and requesting the same mint should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SameMint");
+    }
+
+    // The taker can't rule on their own trade
+    const takerArbitrated = new anchor.BN(2222);
+    await makeArbitrated(takerArbitrated, taker.publicKey, mintB);
+
+    try {
+      await fundArbitrated(takerArbitrated);
+      expect.fail("The arbiter shouldn't be able to take the escrow");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidArbiter");
+    }
+
+    await program.methods
+      .refundArbitrated()
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        makerAtaA: makerAtaA,
+        escrow: arbitratedAccounts(takerArbitrated).arbitratedPda,
+        vaultA: arbitratedAccounts(takerArbitrated).vaultA,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Someone creating the vault first doesn't block funding
+    const precreated = new anchor.BN(2424);
+    const { arbitratedPda, vaultB } = arbitratedAccounts(precreated);
+    await makeArbitrated(precreated, arbiter, mintB);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(maker, vaultB, arbitratedPda, mintB)
+    ));
+
+    await fundArbitrated(precreated);
+
+    const escrowAccount = await program.account.arbitratedEscrow.fetch(arbitratedPda);
+    expect(escrowAccount.status).to.deep.equal({ funded: {} });
+    expect((await provider.connection.getTokenAccountBalance(vaultB)).value.uiAmount).to.equal(receiveAmount);
+
+    await program.methods
+      .confirmArbitrated()
+      .accountsStrict({ party: maker, escrow: arbitratedPda })
+      .rpc();
+    await program.methods
+      .confirmArbitrated()
+      .accountsStrict({ party: taker.publicKey, escrow: arbitratedPda })
+      .signers([taker])
+      .rpc();
+
+    await program.methods
+      .releaseArbitrated()
+      .accountsStrict({
+        caller: maker,
+        maker: maker,
+        taker: taker.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        makerAtaB: makerAtaB,
+        takerAtaA: takerAtaA,
+        takerAtaB: takerAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: treasuryAtaB,
+        escrow: arbitratedPda,
+        vaultA: arbitratedAccounts(precreated).vaultA,
+        vaultB: vaultB,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(arbitratedPda)).to.be.null;
+  });
+
+  it("Releases milestones by approval and by timestamp", async () => {
+    const milestoneSeed = new anchor.BN(1515);
+    const payee = anchor.web3.Keypair.generate().publicKey;
+    const [milestonePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("milestones"), maker.toBuffer(), milestoneSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const milestoneVault = getAssociatedTokenAddressSync(mintA, milestonePda, true);
+    const payeeAta = getAssociatedTokenAddressSync(mintA, payee);
+
+    // The first needs the maker's approval, the second is already unlocked
+    const milestones = [
+      { amount: new anchor.BN(30), unlockAt: null, approved: false, released: false },
+      { amount: new anchor.BN(70), unlockAt: new anchor.BN(Math.floor(Date.now() / 1000) - 60), approved: false, released: false },
+    ];
+
+    await program.methods
+      .makeMilestones(milestoneSeed, payee, milestones)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mint: mintA,
+        makerAta: makerAtaA,
+        escrow: milestonePda,
+        vault: milestoneVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    expect((await provider.connection.getTokenAccountBalance(milestoneVault)).value.uiAmount).to.equal(100);
+
+    const releaseAccounts = {
+      caller: maker,
+      maker: maker,
+      payee: payee,
+      mint: mintA,
+      payeeAta: payeeAta,
+      escrow: milestonePda,
+      vault: milestoneVault,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods
+      .releaseMilestone(1)
+      .accountsStrict(releaseAccounts)
+      .rpc();
+
+    expect((await provider.connection.getTokenAccountBalance(payeeAta)).value.uiAmount).to.equal(70);
+
+    try {
+      await program.methods
+        .releaseMilestone(0)
+        .accountsStrict(releaseAccounts)
+        .rpc();
+      expect.fail("Releasing an unapproved milestone should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotReleasable");
+    }
+
+    await program.methods
+      .approveMilestone(0)
+      .accountsStrict({ maker: maker, escrow: milestonePda })
+      .rpc();
+
+    // The last release sweeps a stray unit along with the tranche
+    await mintTo(provider.connection, provider.wallet.payer, mintA, milestoneVault, provider.wallet.payer, 1);
+
+    await program.methods
+      .releaseMilestone(0)
+      .accountsStrict(releaseAccounts)
+      .rpc();
+
+    expect((await provider.connection.getTokenAccountBalance(payeeAta)).value.uiAmount).to.equal(101);
+    expect(await provider.connection.getAccountInfo(milestonePda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(milestoneVault)).to.be.null;
+  });
+  it("Refunds timestamp-locked milestones only with the payee's signature", async () => {
+    const milestoneSeed = new anchor.BN(2020);
+    const payee = anchor.web3.Keypair.generate();
+    const [milestonePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("milestones"), maker.toBuffer(), milestoneSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const milestoneVault = getAssociatedTokenAddressSync(mintA, milestonePda, true);
+
+    // Unlocks in an hour, so nothing is releasable yet
+    const milestones = [
+      { amount: new anchor.BN(40), unlockAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3600), approved: false, released: false },
+    ];
+
+    await program.methods
+      .makeMilestones(milestoneSeed, payee.publicKey, milestones)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mint: mintA,
+        makerAta: makerAtaA,
+        escrow: milestonePda,
+        vault: milestoneVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const refundAccounts = {
+      maker: maker,
+      payee: null,
+      mint: mintA,
+      makerAta: makerAtaA,
+      escrow: milestonePda,
+      vault: milestoneVault,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      await program.methods
+        .refundMilestones()
+        .accountsStrict(refundAccounts)
+        .rpc();
+      expect.fail("The maker shouldn't be able to refund a timestamp-locked milestone alone");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PayeeSignatureRequired");
+    }
+
+    const makerBalance = (await provider.connection.getTokenAccountBalance(makerAtaA)).value.uiAmount;
+
+    await program.methods
+      .refundMilestones()
+      .accountsStrict({ ...refundAccounts, payee: payee.publicKey })
+      .signers([payee])
+      .rpc();
+
+    expect((await provider.connection.getTokenAccountBalance(makerAtaA)).value.uiAmount).to.equal(makerBalance + 40);
+    expect(await provider.connection.getAccountInfo(milestonePda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(milestoneVault)).to.be.null;
+  });
+
+  it("Settles net of Token-2022 transfer fees", async () => {
+    // Both mints charge 1% on every transfer
+    const createFeeMint = async (authority: anchor.web3.Keypair) => {
+      const mint = anchor.web3.Keypair.generate();
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: maker,
+          newAccountPubkey: mint.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(mint.publicKey, maker, maker, 100, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID),
+        createInitializeMintInstruction(mint.publicKey, 0, authority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ), [mint]);
+      return mint.publicKey;
+    };
+
+    const feeMintA = await createFeeMint(provider.wallet.payer);
+    const feeMintB = await createFeeMint(taker);
+
+    const makerFeeAtaA = getAssociatedTokenAddressSync(feeMintA, maker, false, TOKEN_2022_PROGRAM_ID);
+    const takerFeeAtaB = getAssociatedTokenAddressSync(feeMintB, taker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(maker, makerFeeAtaA, maker, feeMintA, TOKEN_2022_PROGRAM_ID),
+      createAssociatedTokenAccountInstruction(maker, takerFeeAtaB, taker.publicKey, feeMintB, TOKEN_2022_PROGRAM_ID)
+    ));
+    await mintTo(provider.connection, provider.wallet.payer, feeMintA, makerFeeAtaA, provider.wallet.payer, 1000, [], undefined, TOKEN_2022_PROGRAM_ID);
+    await mintTo(provider.connection, taker, feeMintB, takerFeeAtaB, taker, 1000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    const [feeBookHeaderPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book_header"), feeMintA.toBuffer(), feeMintB.toBuffer()],
+      program.programId
+    );
+    const [feeBookPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book"), feeMintA.toBuffer(), feeMintB.toBuffer(), Buffer.alloc(4)],
+      program.programId
+    );
+
+    const makeAccounts = (feeSeed: anchor.BN) => {
+      const [feeEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), maker.toBuffer(), feeSeed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      return {
+        maker: maker,
+        config: configPda,
+        mintA: feeMintA,
+        mintB: feeMintB,
+        makerAtaA: makerFeeAtaA,
+        escrow: feeEscrowPda,
+        vault: getAssociatedTokenAddressSync(feeMintA, feeEscrowPda, true, TOKEN_2022_PROGRAM_ID),
+        bookHeader: feeBookHeaderPda,
+        book: feeBookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+    };
+
+    // A gross deposit records what actually reached the vault
+    const grossSeed = new anchor.BN(1616);
+    const gross = makeAccounts(grossSeed);
+    await program.methods
+      .make(grossSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict(gross)
+      .rpc();
+
+    expect((await program.account.escrow.fetch(gross.escrow)).deposit.toNumber()).to.equal(depositAmount - 1);
+
+    await program.methods
+      .refund()
+      .accountsStrict({
+        maker: maker,
+        mintA: feeMintA,
+        makerAtaA: makerFeeAtaA,
+        escrow: gross.escrow,
+        vault: gross.vault,
+        book: feeBookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // The fee withheld in the vault is harvested to the mint so the vault can close
+    expect(await provider.connection.getAccountInfo(gross.vault)).to.be.null;
+    const withheldAfterRefund = getTransferFeeConfig(
+      await getMint(provider.connection, feeMintA, undefined, TOKEN_2022_PROGRAM_ID)
+    ).withheldAmount;
+    expect(Number(withheldAfterRefund)).to.be.greaterThan(0);
+
+    // A net offer has the fees paid on top, so the vault and the maker get the exact amounts
+    const netSeed = new anchor.BN(1717);
+    const net = makeAccounts(netSeed);
+    await program.methods
+      .make(netSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { net: {} }, 0)
+      .accountsStrict(net)
+      .rpc();
+
+    expect((await program.account.escrow.fetch(net.escrow)).deposit.toNumber()).to.equal(depositAmount);
+    expect((await provider.connection.getTokenAccountBalance(net.vault)).value.uiAmount).to.equal(depositAmount);
+
+    const makerFeeAtaB = getAssociatedTokenAddressSync(feeMintB, maker, false, TOKEN_2022_PROGRAM_ID);
+
+    await program.methods
+      .take(new anchor.BN(depositAmount))
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker,
+        mintA: feeMintA,
+        mintB: feeMintB,
+        takerAtaA: getAssociatedTokenAddressSync(feeMintA, taker.publicKey, false, TOKEN_2022_PROGRAM_ID),
+        takerAtaB: takerFeeAtaB,
+        makerAtaB: makerFeeAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: getAssociatedTokenAddressSync(feeMintB, treasury, false, TOKEN_2022_PROGRAM_ID),
+        escrow: net.escrow,
+        vault: net.vault,
+        book: feeBookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    expect((await provider.connection.getTokenAccountBalance(makerFeeAtaB)).value.uiAmount).to.equal(receiveAmount);
+    expect(await provider.connection.getAccountInfo(net.vault)).to.be.null;
+    const withheldAfterTake = getTransferFeeConfig(
+      await getMint(provider.connection, feeMintA, undefined, TOKEN_2022_PROGRAM_ID)
+    ).withheldAmount;
+    expect(Number(withheldAfterTake)).to.be.greaterThan(Number(withheldAfterRefund));
+  });
+  it("Settles an escrow through a counter-offer", async () => {
+    const counterSeed = new anchor.BN(1818);
+    const [counterEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), counterSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const counterEscrowVault = getAssociatedTokenAddressSync(mintA, counterEscrowPda, true);
+    const [counterOfferPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("counter"), counterEscrowPda.toBuffer(), taker.publicKey.toBuffer()],
+      program.programId
+    );
+    const counterVault = getAssociatedTokenAddressSync(mintB, counterOfferPda, true);
+
+    await program.methods
+      .make(counterSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: counterEscrowPda,
+        vault: counterEscrowVault,
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const makeCounterOffer = async (receive: number) =>
+      program.methods
+        .makeCounterOffer(new anchor.BN(receive))
+        .accountsStrict({
+          taker: taker.publicKey,
+          mintB: mintB,
+          takerAtaB: takerAtaB,
+          escrow: counterEscrowPda,
+          counterOffer: counterOfferPda,
+          counterVault: counterVault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+
+    const balance = async (ata: anchor.web3.PublicKey) =>
+      (await provider.connection.getTokenAccountBalance(ata)).value.uiAmount;
+
+    // The taker changes their mind and takes their mint B back
+    const takerBalanceBBefore = await balance(takerAtaB);
+
+    await makeCounterOffer(receiveAmount / 2);
+    expect(await balance(counterVault)).to.equal(receiveAmount / 2);
+
+    await program.methods
+      .withdrawCounterOffer()
+      .accountsStrict({
+        signer: taker.publicKey,
+        taker: taker.publicKey,
+        mintB: mintB,
+        takerAtaB: takerAtaB,
+        counterOffer: counterOfferPda,
+        counterVault: counterVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(counterOfferPda)).to.be.null;
+    expect(await balance(takerAtaB)).to.equal(takerBalanceBBefore);
+
+    // A better counter-offer that the maker accepts
+    await makeCounterOffer(receiveAmount * 3 / 4);
+
+    const takerBalanceABefore = await balance(takerAtaA);
+    const makerBalanceBBefore = await balance(makerAtaB);
+
+    await program.methods
+      .acceptCounterOffer()
+      .accountsStrict({
+        maker: maker,
+        taker: taker.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        takerAtaA: takerAtaA,
+        makerAtaB: makerAtaB,
+        config: configPda,
+        treasury: treasury,
+        treasuryAtaB: treasuryAtaB,
+        escrow: counterEscrowPda,
+        vault: counterEscrowVault,
+        book: bookPda,
+        counterOffer: counterOfferPda,
+        counterVault: counterVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(counterEscrowPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(counterOfferPda)).to.be.null;
+    expect(await balance(takerAtaA)).to.equal(takerBalanceABefore + depositAmount);
+    expect(await balance(makerAtaB)).to.equal(makerBalanceBBefore + receiveAmount * 3 / 4);
+  });
+  it("Rejects a counter-offer once the escrow is made again with another mint", async () => {
+    const remadeSeed = new anchor.BN(2323);
+    const [remadeEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), remadeSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [counterOfferPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("counter"), remadeEscrowPda.toBuffer(), taker.publicKey.toBuffer()],
+      program.programId
+    );
+    const counterVault = getAssociatedTokenAddressSync(mintB, counterOfferPda, true);
+
+    await program.methods
+      .make(remadeSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: remadeEscrowPda,
+        vault: getAssociatedTokenAddressSync(mintA, remadeEscrowPda, true),
+        bookHeader: bookHeaderPda,
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .makeCounterOffer(new anchor.BN(receiveAmount))
+      .accountsStrict({
+        taker: taker.publicKey,
+        mintB: mintB,
+        takerAtaB: takerAtaB,
+        escrow: remadeEscrowPda,
+        counterOffer: counterOfferPda,
+        counterVault: counterVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+
+    await program.methods
+      .refund()
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        makerAtaA: makerAtaA,
+        escrow: remadeEscrowPda,
+        vault: getAssociatedTokenAddressSync(mintA, remadeEscrowPda, true),
+        book: bookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Same seed, same mint B and deposit, but a worthless mint A
+    const junkMint = await createMint(provider.connection, provider.wallet.payer, maker, null, 0);
+    const makerJunkAta = getAssociatedTokenAddressSync(junkMint, maker);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(maker, makerJunkAta, maker, junkMint)
+    ));
+    await mintTo(provider.connection, provider.wallet.payer, junkMint, makerJunkAta, provider.wallet.payer, depositAmount);
+
+    const [junkBookHeaderPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book_header"), junkMint.toBuffer(), mintB.toBuffer()],
+      program.programId
+    );
+    const [junkBookPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book"), junkMint.toBuffer(), mintB.toBuffer(), Buffer.alloc(4)],
+      program.programId
+    );
+    const junkVault = getAssociatedTokenAddressSync(junkMint, remadeEscrowPda, true);
+
+    await program.methods
+      .make(remadeSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 0)
+      .accountsStrict({
+        maker: maker,
+        config: configPda,
+        mintA: junkMint,
+        mintB: mintB,
+        makerAtaA: makerJunkAta,
+        escrow: remadeEscrowPda,
+        vault: junkVault,
+        bookHeader: junkBookHeaderPda,
+        book: junkBookPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .acceptCounterOffer()
+        .accountsStrict({
+          maker: maker,
+          taker: taker.publicKey,
+          mintA: junkMint,
+          mintB: mintB,
+          takerAtaA: getAssociatedTokenAddressSync(junkMint, taker.publicKey),
+          makerAtaB: makerAtaB,
+          config: configPda,
+          treasury: treasury,
+          treasuryAtaB: treasuryAtaB,
+          escrow: remadeEscrowPda,
+          vault: junkVault,
+          book: junkBookPda,
+          counterOffer: counterOfferPda,
+          counterVault: counterVault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("The counter-offer was for the original mint A");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CounterOfferStale");
+    }
+  });
+
+  it("Only opens offer book pages in order", async () => {
+    const header = await program.account.offerBookHeader.fetch(bookHeaderPda);
+    expect(header.pageCount).to.equal(1);
+
+    const skipSeed = new anchor.BN(2121);
+    const [skipEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), skipSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pageBuffer = Buffer.alloc(4);
+    pageBuffer.writeUInt32LE(2);
+    const [skippedBookPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book"), mintA.toBuffer(), mintB.toBuffer(), pageBuffer],
+      program.programId
+    );
+
+    // Page 1 is next, so page 2 can't be opened yet
+    try {
+      await program.methods
+        .make(skipSeed, new anchor.BN(depositAmount), new anchor.BN(receiveAmount), null, null, null, { gross: {} }, 2)
+        .accountsStrict({
+          maker: maker,
+          config: configPda,
+          mintA: mintA,
+          mintB: mintB,
+          makerAtaA: makerAtaA,
+          escrow: skipEscrowPda,
+          vault: getAssociatedTokenAddressSync(mintA, skipEscrowPda, true),
+          bookHeader: bookHeaderPda,
+          book: skippedBookPda,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Skipping a book page should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBookPage");
+    }
   });
 });
\ No newline at end of file
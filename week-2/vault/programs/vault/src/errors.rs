@@ -0,0 +1,57 @@
+use anchor_lang::error_code;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Vault is still locked")]
+    VaultLocked,
+    #[msg("Lock can only be extended")]
+    LockCannotBeShortened,
+    #[msg("Too many owners")]
+    TooManyOwners,
+    #[msg("Duplicate owner")]
+    DuplicateOwner,
+    #[msg("Invalid threshold")]
+    InvalidThreshold,
+    #[msg("Signer is not an owner")]
+    NotAnOwner,
+    #[msg("Proposal already approved by this owner")]
+    AlreadyApproved,
+    #[msg("Approval threshold not reached")]
+    ThresholdNotReached,
+    #[msg("Allowance period must be positive and at most a year")]
+    InvalidPeriod,
+    #[msg("Allowance exceeded for this period")]
+    AllowanceExceeded,
+    #[msg("Inactivity period must be positive")]
+    InvalidInactivityPeriod,
+    #[msg("Signer is not the beneficiary")]
+    NotBeneficiary,
+    #[msg("Owner is still active")]
+    OwnerStillActive,
+    #[msg("Stream must end after it starts and have a positive rate")]
+    InvalidStream,
+    #[msg("Nothing to withdraw")]
+    NothingToWithdraw,
+    #[msg("Overflow")]
+    Overflow,
+    #[msg("Vault registry is full")]
+    RegistryFull,
+    #[msg("Withdrawals must go through the withdrawal queue")]
+    WithdrawalQueueActive,
+    #[msg("Withdrawal queue is not enabled")]
+    WithdrawalQueueDisabled,
+    #[msg("Withdrawal delay must be positive, within the maximum and can only be extended")]
+    InvalidWithdrawalDelay,
+    #[msg("Changing the guardian needs the current guardian's signature")]
+    GuardianSignatureRequired,
+    #[msg("Withdrawal request is for a different asset")]
+    InvalidWithdrawalRequest,
+    #[msg("Withdrawal request is not executable yet")]
+    WithdrawalNotReady,
+    #[msg("Signer is neither the owner nor the guardian")]
+    NotOwnerOrGuardian,
+    #[msg("Memo is too long")]
+    MemoTooLong,
+    #[msg("Token vault accounts don't match the vault")]
+    InvalidTokenVault,
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DepositForEvent {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub memo: String,
+}
+
+#[event]
+pub struct InitializeEvent {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub index: u64,
+    pub unlock_at: Option<i64>,
+}
+
+#[event]
+pub struct DepositEvent {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct CloseEvent {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+}
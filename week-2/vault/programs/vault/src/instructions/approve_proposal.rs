@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::VaultError,
+    state::{MultisigState, Proposal},
+};
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig_state.creator.as_ref()],
+        bump = multisig_state.state_bump,
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal", 
+            multisig_state.key().as_ref(), 
+            proposal.index.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> ApproveProposal<'info> {
+    pub fn approve_proposal(&mut self) -> Result<()> {
+
+        let owner_bit = 1 << self.multisig_state.owner_index(&self.owner.key())?;
+
+        require!(
+            self.proposal.approvals & owner_bit == 0,
+            VaultError::AlreadyApproved
+        );
+
+        self.proposal.approvals |= owner_bit;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    events::WithdrawEvent,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut
+    )]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()], 
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        close = user,
+        has_one = recipient,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.key().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelStream<'info> {
+    pub fn cancel_stream(&mut self) -> Result<()> {
+
+        self.vault_state.touch()?;
+
+        // Whatever has vested so far still belongs to the recipient, 
+        // the unvested remainder stays in the vault
+        let amount = self.stream.withdrawable(Clock::get()?.unix_timestamp)?;
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        self.vault_state.check_unlocked()?;
+        self.vault_state.record_withdrawal(amount)?;
+
+        let vault_state_key = self.vault_state.key();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault", 
+            vault_state_key.as_ref(), 
+            &[self.vault_state.vault_bump]
+        ]];
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+             from: self.vault.to_account_info(), 
+             to: self.recipient.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new_with_signer(
+                system_program, 
+                cpi_accounts, 
+                signer_seeds
+            );
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(WithdrawEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.user.key(),
+            amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::VaultError,
+    state::{VaultState, WithdrawalRequest},
+};
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        constraint = authority.key() == owner.key() || 
+            Some(authority.key()) == vault_state.guardian @ VaultError::NotOwnerOrGuardian
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut
+    )]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"state", owner.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    // Rent goes back to the owner who paid for the request
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"request", 
+            vault_state.key().as_ref(), 
+            request.index.to_le_bytes().as_ref()
+        ],
+        bump = request.bump,
+    )]
+    pub request: Account<'info, WithdrawalRequest>,
+}
+
+impl<'info> CancelWithdrawal<'info> {
+    pub fn cancel_withdrawal(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
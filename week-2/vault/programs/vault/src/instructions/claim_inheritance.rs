@@ -0,0 +1,174 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, 
+    Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    errors::VaultError,
+    events::CloseEvent,
+    state::{VaultRegistry, VaultState},
+    utils::harvest_withheld_fees,
+};
+
+// Each token vault is passed as mint (writable), vault ATA, beneficiary token account and token program
+pub const TOKEN_VAULT_ACCOUNTS: usize = 4;
+
+#[derive(Accounts)]
+pub struct ClaimInheritance<'info> {    
+    #[account(
+        mut
+    )]
+    pub beneficiary: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.beneficiary == Some(beneficiary.key()) @ VaultError::NotBeneficiary,
+        close = beneficiary,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", owner.key().as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, VaultRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimInheritance<'info> {
+    pub fn claim_inheritance(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now - self.vault_state.last_active > self.vault_state.inactivity_period,
+            VaultError::OwnerStillActive
+        );
+
+        self.registry.unregister(self.vault_state.index);
+
+        // vault_state is closed below, so any token vault left behind could never be signed for again
+        require!(
+            remaining_accounts.len() % TOKEN_VAULT_ACCOUNTS == 0,
+            VaultError::InvalidTokenVault
+        );
+
+        for accounts in remaining_accounts.chunks(TOKEN_VAULT_ACCOUNTS) {
+            self.sweep_token_vault(accounts)?;
+        }
+
+        let amount = self.vault.lamports();
+        self.vault_state.record_withdrawal(amount)?;
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.beneficiary.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            self.vault_state.to_account_info().key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ]];
+        
+        let cpi_ctx = 
+            CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(CloseEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.owner.key(),
+            amount,
+            total_deposited: self.vault_state.total_deposited,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        Ok(())
+    }
+
+    fn sweep_token_vault(&self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let vault_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        let beneficiary_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        let token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+        require_keys_eq!(*accounts[0].owner, token_program.key(), VaultError::InvalidTokenVault);
+        require!(
+            vault_ata.mint == mint.key() && vault_ata.owner == self.vault_state.key(),
+            VaultError::InvalidTokenVault
+        );
+        require!(
+            beneficiary_ata.mint == mint.key() && beneficiary_ata.owner == self.beneficiary.key(),
+            VaultError::InvalidTokenVault
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"state",
+            self.owner.to_account_info().key.as_ref(),
+            &self.vault_state.index.to_le_bytes(),
+            &[self.vault_state.state_bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: vault_ata.to_account_info(),
+            mint: mint.to_account_info(),
+            to: beneficiary_ata.to_account_info(),
+            authority: self.vault_state.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, vault_ata.amount, mint.decimals)?;
+
+        harvest_withheld_fees(
+            vault_ata.to_account_info(),
+            mint.to_account_info(),
+            token_program.to_account_info(),
+        )?;
+
+        let close_accounts = CloseAccount {
+            account: vault_ata.to_account_info(),
+            destination: self.beneficiary.to_account_info(),
+            authority: self.vault_state.to_account_info(),
+        };
+
+        let close_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    errors::VaultError,
+    events::CloseEvent,
+    state::{VaultRegistry, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct Close<'info> {    
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+        close = user,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", user.key().as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, VaultRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Close<'info> {
+    pub fn close(&mut self) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        // With the queue enabled the vault has to be emptied through requests first
+        if self.vault_state.withdrawal_delay.is_some() {
+            require!(
+                self.vault.lamports() <= Rent::get()?.minimum_balance(0),
+                VaultError::WithdrawalQueueActive
+            );
+        }
+
+        self.registry.unregister(self.vault_state.index);
+
+        let amount = self.vault.lamports();
+        self.vault_state.record_withdrawal(amount)?;
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.user.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            self.vault_state.to_account_info().key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ]];
+        
+        let cpi_ctx = 
+            CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(CloseEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.user.key(),
+            amount,
+            total_deposited: self.vault_state.total_deposited,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, 
+        Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{errors::VaultError, state::VaultState, utils::harvest_withheld_fees};
+
+#[derive(Accounts)]
+pub struct CloseTokenVault<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault_state,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseTokenVault<'info> {
+    pub fn close_token_vault(&mut self) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        // With the queue enabled the tokens have to leave through requests first
+        if self.vault_state.withdrawal_delay.is_some() {
+            require!(self.vault_ata.amount == 0, VaultError::WithdrawalQueueActive);
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"state",
+            self.user.to_account_info().key.as_ref(),
+            &self.vault_state.index.to_le_bytes(),
+            &[self.vault_state.state_bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.user_ata.to_account_info(),
+            authority: self.vault_state.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.vault_ata.amount, self.mint.decimals)?;
+
+        harvest_withheld_fees(
+            self.vault_ata.to_account_info(),
+            self.mint.to_account_info(),
+            self.token_program.to_account_info(),
+        )?;
+
+        let close_accounts = CloseAccount {
+            account: self.vault_ata.to_account_info(),
+            destination: self.user.to_account_info(),
+            authority: self.vault_state.to_account_info(),
+        };
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::VaultError,
+    state::{VaultState, MAX_WITHDRAWAL_DELAY},
+};
+
+#[derive(Accounts)]
+pub struct ConfigureWithdrawalQueue<'info> {
+    pub user: Signer<'info>,
+
+    // Only needed to replace or remove an existing guardian
+    pub guardian: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> ConfigureWithdrawalQueue<'info> {
+    pub fn configure_withdrawal_queue(
+        &mut self, 
+        delay: i64, 
+        guardian: Option<Pubkey>
+    ) -> Result<()> {
+
+        // A stolen key must not be able to shorten or switch off the delay
+        let current = self.vault_state.withdrawal_delay.unwrap_or(0);
+        require!(
+            delay > 0 && delay >= current && delay <= MAX_WITHDRAWAL_DELAY,
+            VaultError::InvalidWithdrawalDelay
+        );
+
+        // Nor to get rid of the guardian that would cancel its requests
+        if let Some(current) = self.vault_state.guardian {
+            if guardian != Some(current) {
+                require!(
+                    self.guardian.as_ref().is_some_and(|signer| signer.key() == current),
+                    VaultError::GuardianSignatureRequired
+                );
+            }
+        }
+
+        self.vault_state.withdrawal_delay = Some(delay);
+        self.vault_state.guardian = guardian;
+
+        self.vault_state.touch()
+    }
+}
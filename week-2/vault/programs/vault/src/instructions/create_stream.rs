@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::VaultError,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct CreateStream<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.as_ref()],
+        bump,
+        space = Stream::DISCRIMINATOR.len() + 
+            Stream::INIT_SPACE,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStream<'info> {
+    pub fn create_stream(
+        &mut self, 
+        recipient: Pubkey, 
+        rate: u64, 
+        start: i64, 
+        end: i64, 
+        bumps: &CreateStreamBumps
+    ) -> Result<()> {
+
+        // A stream is an outflow the owner sets up alone, so it can't dodge the withdrawal queue
+        self.vault_state.check_instant_withdrawal()?;
+        require!(rate > 0 && end > start, VaultError::InvalidStream);
+
+        // Everything the stream will ever vest has to fit, or withdrawals
+        // would start failing partway through
+        let duration = end.checked_sub(start).ok_or(VaultError::Overflow)?;
+        rate.checked_mul(duration as u64).ok_or(VaultError::Overflow)?;
+
+        self.stream.set_inner(Stream {
+            vault_state: self.vault_state.key(),
+            recipient,
+            rate,
+            start,
+            end,
+            withdrawn: 0,
+            bump: bumps.stream,
+        });
+
+        self.vault_state.touch()
+    }
+}
@@ -0,0 +1,89 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    events::WithdrawEvent,
+    state::{Allowance, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    #[account(
+        mut
+    )]
+    pub delegate: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()], 
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"allowance", vault_state.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelegateWithdraw<'info> {
+    pub fn delegate_withdraw(&mut self, amount: u64) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        self.allowance.spend(amount, Clock::get()?.unix_timestamp)?;
+        self.vault_state.record_withdrawal(amount)?;
+
+        let vault_state_key = self.vault_state.key();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault", 
+            vault_state_key.as_ref(), 
+            &[self.vault_state.vault_bump]
+        ]];
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+             from: self.vault.to_account_info(), 
+             to: self.delegate.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new_with_signer(
+                system_program, 
+                cpi_accounts, 
+                signer_seeds
+            );
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(WithdrawEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.owner.key(),
+            amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        Ok(())
+    }
+}
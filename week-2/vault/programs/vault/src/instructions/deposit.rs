@@ -0,0 +1,63 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{events::DepositEvent, state::VaultState};
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()], 
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+
+        self.vault_state.touch()?;
+        self.vault_state.record_deposit(amount)?;
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+            from: self.user.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new(system_program, cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(DepositEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.user.key(),
+            amount,
+            total_deposited: self.vault_state.total_deposited,
+        });
+
+        Ok(())
+    }
+}
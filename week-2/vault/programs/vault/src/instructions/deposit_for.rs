@@ -0,0 +1,69 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{errors::VaultError, events::DepositForEvent, state::VaultState};
+
+pub const MAX_MEMO_LEN: usize = 64;
+
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    #[account(
+        mut
+    )]
+    pub payer: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()], 
+        bump = vault_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositFor<'info> {
+    pub fn deposit_for(&mut self, amount: u64, memo: String) -> Result<()> {
+
+        require!(memo.len() <= MAX_MEMO_LEN, VaultError::MemoTooLong);
+
+        self.vault_state.record_deposit(amount)?;
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+            from: self.payer.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new(system_program, cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(DepositForEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.owner.key(),
+            payer: self.payer.key(),
+            amount,
+            memo,
+        });
+
+        Ok(())
+    }
+}
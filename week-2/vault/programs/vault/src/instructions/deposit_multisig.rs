@@ -0,0 +1,49 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::state::MultisigState;
+
+#[derive(Accounts)]
+pub struct DepositMultisig<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", multisig_state.key().as_ref()], 
+        bump = multisig_state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig_state.creator.as_ref()],
+        bump = multisig_state.state_bump,
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositMultisig<'info> {
+    pub fn deposit_multisig(&mut self, amount: u64) -> Result<()> {
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+            from: self.user.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new(system_program, cpi_accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
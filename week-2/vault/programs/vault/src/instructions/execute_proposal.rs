@@ -0,0 +1,93 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    errors::VaultError,
+    state::{MultisigState, Proposal},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", multisig_state.key().as_ref()], 
+        bump = multisig_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig_state.creator.as_ref()],
+        bump = multisig_state.state_bump,
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        mut,
+        has_one = proposer,
+        has_one = recipient,
+        close = proposer,
+        seeds = [
+            b"proposal", 
+            multisig_state.key().as_ref(), 
+            proposal.index.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut
+    )]
+    pub proposer: SystemAccount<'info>,
+
+    #[account(
+        mut
+    )]
+    pub recipient: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn execute_proposal(&mut self) -> Result<()> {
+
+        self.multisig_state.owner_index(&self.owner.key())?;
+
+        require!(
+            self.proposal.approval_count() >= self.multisig_state.threshold,
+            VaultError::ThresholdNotReached
+        );
+
+        let multisig_state_key = self.multisig_state.key();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault", 
+            multisig_state_key.as_ref(), 
+            &[self.multisig_state.vault_bump]
+        ]];
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+             from: self.vault.to_account_info(), 
+             to: self.recipient.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new_with_signer(
+                system_program, 
+                cpi_accounts, 
+                signer_seeds
+            );
+
+        transfer(cpi_ctx, self.proposal.amount)
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        transfer_checked, Mint, TokenAccount, 
+        TokenInterface, TransferChecked
+    },
+};
+
+use crate::{
+    errors::VaultError,
+    state::{VaultState, WithdrawalRequest},
+};
+
+// Token counterpart of execute_withdrawal, pays a queued request out of the
+// vault's ATA for the request's mint
+#[derive(Accounts)]
+pub struct ExecuteTokenWithdrawal<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault_state,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        has_one = recipient,
+        constraint = request.mint == Some(mint.key()) @ VaultError::InvalidWithdrawalRequest,
+        seeds = [
+            b"request", 
+            vault_state.key().as_ref(), 
+            request.index.to_le_bytes().as_ref()
+        ],
+        bump = request.bump,
+    )]
+    pub request: Account<'info, WithdrawalRequest>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteTokenWithdrawal<'info> {
+    pub fn execute_token_withdrawal(&mut self) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        require!(
+            Clock::get()?.unix_timestamp >= self.request.executable_at,
+            VaultError::WithdrawalNotReady
+        );
+
+        self.vault_state.touch()?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"state",
+            self.user.to_account_info().key.as_ref(),
+            &self.vault_state.index.to_le_bytes(),
+            &[self.vault_state.state_bump],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.vault_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.recipient_ata.to_account_info(),
+            authority: self.vault_state.to_account_info(),
+        };
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.request.amount, self.mint.decimals)
+    }
+}
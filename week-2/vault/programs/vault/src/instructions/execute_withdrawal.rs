@@ -0,0 +1,105 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    errors::VaultError,
+    events::WithdrawEvent,
+    state::{VaultState, WithdrawalRequest},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut
+    )]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()], 
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        close = user,
+        has_one = recipient,
+        constraint = request.mint.is_none() @ VaultError::InvalidWithdrawalRequest,
+        seeds = [
+            b"request", 
+            vault_state.key().as_ref(), 
+            request.index.to_le_bytes().as_ref()
+        ],
+        bump = request.bump,
+    )]
+    pub request: Account<'info, WithdrawalRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteWithdrawal<'info> {
+    pub fn execute_withdrawal(&mut self) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        require!(
+            Clock::get()?.unix_timestamp >= self.request.executable_at,
+            VaultError::WithdrawalNotReady
+        );
+
+        self.vault_state.touch()?;
+        self.vault_state.record_withdrawal(self.request.amount)?;
+
+        let vault_state_key = self.vault_state.key();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault", 
+            vault_state_key.as_ref(), 
+            &[self.vault_state.vault_bump]
+        ]];
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+             from: self.vault.to_account_info(), 
+             to: self.recipient.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new_with_signer(
+                system_program, 
+                cpi_accounts, 
+                signer_seeds
+            );
+
+        transfer(cpi_ctx, self.request.amount)?;
+
+        emit!(WithdrawEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.user.key(),
+            amount: self.request.amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        Ok(())
+    }
+}
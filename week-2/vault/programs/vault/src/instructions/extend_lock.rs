@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::VaultError, state::VaultState};
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> ExtendLock<'info> {
+    pub fn extend_lock(&mut self, unlock_at: i64) -> Result<()> {
+
+        // The lock can only be pushed further out, never brought forward
+        if let Some(current) = self.vault_state.unlock_at {
+            require!(unlock_at > current, VaultError::LockCannotBeShortened);
+        }
+
+        self.vault_state.unlock_at = Some(unlock_at);
+
+        Ok(())
+    }
+}
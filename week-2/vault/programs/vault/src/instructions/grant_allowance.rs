@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::VaultError,
+    state::{Allowance, VaultState, MAX_ALLOWANCE_PERIOD},
+};
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct GrantAllowance<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"allowance", vault_state.key().as_ref(), delegate.as_ref()],
+        bump,
+        space = Allowance::DISCRIMINATOR.len() + 
+            Allowance::INIT_SPACE,
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GrantAllowance<'info> {
+    pub fn grant_allowance(
+        &mut self, 
+        delegate: Pubkey, 
+        amount: u64, 
+        period: i64, 
+        bumps: &GrantAllowanceBumps
+    ) -> Result<()> {
+
+        self.vault_state.check_instant_withdrawal()?;
+        require!(period > 0 && period <= MAX_ALLOWANCE_PERIOD, VaultError::InvalidPeriod);
+
+        self.allowance.set_inner(Allowance {
+            vault_state: self.vault_state.key(),
+            delegate,
+            amount,
+            period,
+            period_start: Clock::get()?.unix_timestamp,
+            spent: 0,
+            bump: bumps.allowance,
+        });
+
+        self.vault_state.touch()
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    events::InitializeEvent,
+    state::{VaultRegistry, VaultState},
+};
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct Initialize<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"state", user.key().as_ref(), index.to_le_bytes().as_ref()], 
+        bump,
+        space = VaultState::DISCRIMINATOR.len() + 
+            VaultState::INIT_SPACE,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"registry", user.key().as_ref()],
+        bump,
+        space = VaultRegistry::DISCRIMINATOR.len() + 
+            VaultRegistry::INIT_SPACE,
+    )]
+    pub registry: Account<'info, VaultRegistry>,
+
+    /// CHECK: Stores the user's funds
+    #[account(
+        mut,      
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(
+        &mut self, 
+        index: u64, 
+        unlock_at: Option<i64>, 
+        bumps: &InitializeBumps
+    ) -> Result<()> {
+
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+
+        let system_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.user.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(system_program, cpi_accounts);
+
+        transfer(cpi_ctx, rent_exempt)?;
+
+        self.registry.bump = bumps.registry;
+        self.registry.register(index)?;
+
+        self.vault_state.index = index;
+        self.vault_state.vault_bump = bumps.vault;
+        self.vault_state.state_bump = bumps.vault_state;
+        self.vault_state.unlock_at = unlock_at;
+        self.vault_state.last_active = Clock::get()?.unix_timestamp;
+
+        // Recorded so the whole balance paid out on close reconciles
+        self.vault_state.record_deposit(rent_exempt)?;
+
+        emit!(InitializeEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.user.key(),
+            index,
+            unlock_at,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    errors::VaultError,
+    state::{MultisigState, MAX_OWNERS},
+};
+
+#[derive(Accounts)]
+pub struct InitializeMultisig<'info> {
+    #[account(
+        mut
+    )]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"multisig", creator.key().as_ref()], 
+        bump,
+        space = MultisigState::DISCRIMINATOR.len() + 
+            MultisigState::INIT_SPACE,
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        mut,      
+        seeds = [b"vault", multisig_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMultisig<'info> {
+    pub fn initialize_multisig(
+        &mut self, 
+        owners: Vec<Pubkey>, 
+        threshold: u8, 
+        bumps: &InitializeMultisigBumps
+    ) -> Result<()> {
+
+        require!(owners.len() <= MAX_OWNERS, VaultError::TooManyOwners);
+        require!(
+            threshold > 0 && usize::from(threshold) <= owners.len(),
+            VaultError::InvalidThreshold
+        );
+
+        for (i, owner) in owners.iter().enumerate() {
+            require!(!owners[..i].contains(owner), VaultError::DuplicateOwner);
+        }
+
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+
+        let system_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.creator.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(system_program, cpi_accounts);
+
+        transfer(cpi_ctx, rent_exempt)?;
+
+        self.multisig_state.set_inner(MultisigState {
+            creator: self.creator.key(),
+            owners,
+            threshold,
+            proposal_count: 0,
+            vault_bump: bumps.vault,
+            state_bump: bumps.multisig_state,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+pub mod initialize;
+pub use initialize::*;
+
+pub mod deposit;
+pub use deposit::*;
+
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod close;
+pub use close::*;
+
+pub mod extend_lock;
+pub use extend_lock::*;
+
+pub mod deposit_token;
+pub use deposit_token::*;
+
+pub mod withdraw_token;
+pub use withdraw_token::*;
+
+pub mod close_token_vault;
+pub use close_token_vault::*;
+
+pub mod initialize_multisig;
+pub use initialize_multisig::*;
+
+pub mod deposit_multisig;
+pub use deposit_multisig::*;
+
+pub mod propose_withdrawal;
+pub use propose_withdrawal::*;
+
+pub mod approve_proposal;
+pub use approve_proposal::*;
+
+pub mod execute_proposal;
+pub use execute_proposal::*;
+
+pub mod grant_allowance;
+pub use grant_allowance::*;
+
+pub mod revoke_allowance;
+pub use revoke_allowance::*;
+
+pub mod delegate_withdraw;
+pub use delegate_withdraw::*;
+
+pub mod heartbeat;
+pub use heartbeat::*;
+
+pub mod set_beneficiary;
+pub use set_beneficiary::*;
+
+pub mod claim_inheritance;
+pub use claim_inheritance::*;
+
+pub mod create_stream;
+pub use create_stream::*;
+
+pub mod withdraw_streamed;
+pub use withdraw_streamed::*;
+
+pub mod cancel_stream;
+pub use cancel_stream::*;
+
+pub mod configure_withdrawal_queue;
+pub use configure_withdrawal_queue::*;
+
+pub mod request_withdrawal;
+pub use request_withdrawal::*;
+
+pub mod execute_withdrawal;
+pub use execute_withdrawal::*;
+
+pub mod execute_token_withdrawal;
+pub use execute_token_withdrawal::*;
+
+pub mod cancel_withdrawal;
+pub use cancel_withdrawal::*;
+
+pub mod deposit_for;
+pub use deposit_for::*;
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{MultisigState, Proposal};
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(
+        mut
+    )]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig_state.creator.as_ref()],
+        bump = multisig_state.state_bump,
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [
+            b"proposal", 
+            multisig_state.key().as_ref(), 
+            multisig_state.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = Proposal::DISCRIMINATOR.len() + 
+            Proposal::INIT_SPACE,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeWithdrawal<'info> {
+    pub fn propose_withdrawal(
+        &mut self, 
+        amount: u64, 
+        recipient: Pubkey, 
+        bumps: &ProposeWithdrawalBumps
+    ) -> Result<()> {
+
+        let owner_index = self.multisig_state.owner_index(&self.proposer.key())?;
+
+        // Proposing counts as the proposer's approval
+        self.proposal.set_inner(Proposal {
+            multisig: self.multisig_state.key(),
+            index: self.multisig_state.proposal_count,
+            proposer: self.proposer.key(),
+            recipient,
+            amount,
+            approvals: 1 << owner_index,
+            bump: bumps.proposal,
+        });
+
+        self.multisig_state.proposal_count += 1;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::VaultError,
+    state::{VaultState, WithdrawalRequest},
+};
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"request", 
+            vault_state.key().as_ref(), 
+            vault_state.request_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = WithdrawalRequest::DISCRIMINATOR.len() + 
+            WithdrawalRequest::INIT_SPACE,
+    )]
+    pub request: Account<'info, WithdrawalRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestWithdrawal<'info> {
+    pub fn request_withdrawal(
+        &mut self, 
+        amount: u64, 
+        recipient: Pubkey, 
+        mint: Option<Pubkey>,
+        bumps: &RequestWithdrawalBumps
+    ) -> Result<()> {
+
+        let delay = self.vault_state
+            .withdrawal_delay
+            .ok_or(VaultError::WithdrawalQueueDisabled)?;
+
+        let executable_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(delay)
+            .ok_or(VaultError::Overflow)?;
+
+        self.request.set_inner(WithdrawalRequest {
+            vault_state: self.vault_state.key(),
+            index: self.vault_state.request_count,
+            recipient,
+            amount,
+            mint,
+            executable_at,
+            bump: bumps.request,
+        });
+
+        self.vault_state.request_count += 1;
+
+        self.vault_state.touch()
+    }
+}
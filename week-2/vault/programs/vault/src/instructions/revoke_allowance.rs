@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Allowance, VaultState};
+
+#[derive(Accounts)]
+pub struct RevokeAllowance<'info> {
+    #[account(
+        mut
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"allowance", vault_state.key().as_ref(), allowance.delegate.as_ref()],
+        bump = allowance.bump,
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+
+impl<'info> RevokeAllowance<'info> {
+    pub fn revoke_allowance(&mut self) -> Result<()> {
+        self.vault_state.touch()
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::VaultError, state::VaultState};
+
+#[derive(Accounts)]
+pub struct SetBeneficiary<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> SetBeneficiary<'info> {
+    pub fn set_beneficiary(
+        &mut self, 
+        beneficiary: Option<Pubkey>, 
+        inactivity_period: i64
+    ) -> Result<()> {
+
+        if beneficiary.is_some() {
+            // Otherwise a stolen key could name itself with a tiny period and claim right away
+            self.vault_state.check_instant_withdrawal()?;
+            require!(inactivity_period > 0, VaultError::InvalidInactivityPeriod);
+        }
+
+        self.vault_state.beneficiary = beneficiary;
+        self.vault_state.inactivity_period = inactivity_period;
+
+        self.vault_state.touch()
+    }
+}
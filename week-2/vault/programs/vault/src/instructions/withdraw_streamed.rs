@@ -0,0 +1,94 @@
+use anchor_lang::{
+    prelude::*, 
+    system_program::{
+        transfer, 
+        Transfer
+    }
+};
+
+use crate::{
+    errors::VaultError,
+    events::WithdrawEvent,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawStreamed<'info> {
+    #[account(
+        mut
+    )]
+    pub recipient: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()], 
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref(), vault_state.index.to_le_bytes().as_ref()],
+        bump = vault_state.state_bump,
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.key().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawStreamed<'info> {
+    pub fn withdraw_streamed(&mut self) -> Result<()> {
+
+        self.vault_state.check_unlocked()?;
+
+        let amount = self.stream.withdrawable(Clock::get()?.unix_timestamp)?;
+
+        require!(amount > 0, VaultError::NothingToWithdraw);
+
+        self.stream.withdrawn += amount;
+        self.vault_state.record_withdrawal(amount)?;
+
+        let vault_state_key = self.vault_state.key();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault", 
+            vault_state_key.as_ref(), 
+            &[self.vault_state.vault_bump]
+        ]];
+
+        let system_program = 
+            self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+             from: self.vault.to_account_info(), 
+             to: self.recipient.to_account_info(),
+        };
+
+        let cpi_ctx = 
+            CpiContext::new_with_signer(
+                system_program, 
+                cpi_accounts, 
+                signer_seeds
+            );
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(WithdrawEvent {
+            vault_state: self.vault_state.key(),
+            owner: self.owner.key(),
+            amount,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
-use anchor_lang::{
-    prelude::*, 
-    system_program::{
-        transfer, 
-        Transfer
-    }
-};
+use anchor_lang::prelude::*;
+
+mod errors;
+mod events;
+mod instructions;
+mod state;
+mod utils;
+
+use instructions::*;
 
 declare_id!("7TcfRkKKwnPEQyTDkrbuBdPfZPZLvjGprz3wvy61Kyrf");
 
@@ -12,8 +14,12 @@ declare_id!("7TcfRkKKwnPEQyTDkrbuBdPfZPZLvjGprz3wvy61Kyrf");
 pub mod vault {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        ctx.accounts.initialize(&ctx.bumps)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        index: u64,
+        unlock_at: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts.initialize(index, unlock_at, &ctx.bumps)
     }
 
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
@@ -27,203 +33,134 @@ pub mod vault {
     pub fn close(ctx: Context<Close>) -> Result<()> {
         ctx.accounts.close()
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        mut
-    )]
-    pub user: Signer<'info>,
-
-    #[account(
-        init,
-        payer = user,
-        seeds = [b"state", user.key().as_ref()], 
-        bump,
-        space = VaultState::DISCRIMINATOR.len() + 
-            VaultState::INIT_SPACE,
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    /// CHECK: Stores the user's funds
-    #[account(
-        mut,      
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump,
-    )]
-    pub vault: SystemAccount<'info>,
-
-    pub system_program: Program<'info, System>,
-}
-
-impl<'info> Initialize<'info> {
-    pub fn initialize(&mut self, bumps: &InitializeBumps) -> Result<()> {
-
-        let rent_exempt = Rent::get()?.minimum_balance(0);
-
-        let system_program = self.system_program.to_account_info();
-        let cpi_accounts = Transfer {
-            from: self.user.to_account_info(),
-            to: self.vault.to_account_info(),
-        };
+    pub fn extend_lock(ctx: Context<ExtendLock>, unlock_at: i64) -> Result<()> {
+        ctx.accounts.extend_lock(unlock_at)
+    }
 
-        let cpi_ctx = CpiContext::new(system_program, cpi_accounts);
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_token(amount)
+    }
 
-        transfer(cpi_ctx, rent_exempt)?;
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_token(amount)
+    }
 
-        self.vault_state.vault_bump = bumps.vault;
-        self.vault_state.state_bump = bumps.vault_state;
+    pub fn close_token_vault(ctx: Context<CloseTokenVault>) -> Result<()> {
+        ctx.accounts.close_token_vault()
+    }
 
-        Ok(())
+    pub fn initialize_multisig(
+        ctx: Context<InitializeMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts.initialize_multisig(owners, threshold, &ctx.bumps)
     }
-}
 
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(
-        mut
-    )]
-    pub user: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()], 
-        bump = vault_state.vault_bump,
-    )]
-    pub vault: SystemAccount<'info>,
-
-    #[account(
-        seeds = [b"state", user.key().as_ref()],
-        bump = vault_state.state_bump,
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub fn deposit_multisig(ctx: Context<DepositMultisig>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_multisig(amount)
+    }
 
-impl<'info> Deposit<'info> {
-    pub fn deposit(&mut self, amount: u64) -> Result<()> {
-        let system_program = 
-            self.system_program.to_account_info();
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        amount: u64,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.propose_withdrawal(amount, recipient, &ctx.bumps)
+    }
 
-        let cpi_accounts = Transfer {
-            from: self.user.to_account_info(),
-            to: self.vault.to_account_info(),
-        };
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        ctx.accounts.approve_proposal()
+    }
 
-        let cpi_ctx = 
-            CpiContext::new(system_program, cpi_accounts);
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        ctx.accounts.execute_proposal()
+    }
 
-        transfer(cpi_ctx, amount)
+    pub fn grant_allowance(
+        ctx: Context<GrantAllowance>,
+        delegate: Pubkey,
+        amount: u64,
+        period: i64,
+    ) -> Result<()> {
+        ctx.accounts.grant_allowance(delegate, amount, period, &ctx.bumps)
     }
-}
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(
-        mut
-    )]
-    pub user: Signer<'info>,
-
-   #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()], 
-        bump = vault_state.vault_bump
-    )]
-   pub vault: SystemAccount<'info>,
-
-   #[account(
-        seeds = [b"state", user.key().as_ref()],
-        bump = vault_state.state_bump,
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub fn revoke_allowance(ctx: Context<RevokeAllowance>) -> Result<()> {
+        ctx.accounts.revoke_allowance()
+    }
 
-impl<'info> Withdraw<'info> {
-    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+    pub fn delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.delegate_withdraw(amount)
+    }
 
-        let vault_state_key = self.vault_state.key();
+    pub fn set_beneficiary(
+        ctx: Context<SetBeneficiary>,
+        beneficiary: Option<Pubkey>,
+        inactivity_period: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_beneficiary(beneficiary, inactivity_period)
+    }
 
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            b"vault", 
-            vault_state_key.as_ref(), 
-            &[self.vault_state.vault_bump]
-        ]];
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        ctx.accounts.heartbeat()
+    }
 
-        let system_program = 
-            self.system_program.to_account_info();
+    pub fn claim_inheritance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimInheritance<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.claim_inheritance(ctx.remaining_accounts)
+    }
 
-        let cpi_accounts = Transfer {
-             from: self.vault.to_account_info(), 
-             to: self.user.to_account_info(),
-        };
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        recipient: Pubkey,
+        rate: u64,
+        start: i64,
+        end: i64,
+    ) -> Result<()> {
+        ctx.accounts.create_stream(recipient, rate, start, end, &ctx.bumps)
+    }
 
-        let cpi_ctx = 
-            CpiContext::new_with_signer(
-                system_program, 
-                cpi_accounts, 
-                signer_seeds
-            );
+    pub fn withdraw_streamed(ctx: Context<WithdrawStreamed>) -> Result<()> {
+        ctx.accounts.withdraw_streamed()
+    }
 
-        transfer(cpi_ctx, amount)
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        ctx.accounts.cancel_stream()
     }
-}
 
-#[derive(Accounts)]
-pub struct Close<'info> {    
-    #[account(
-        mut
-    )]
-    pub user: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump = vault_state.vault_bump,
-    )]
-    pub vault: SystemAccount<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"state", user.key().as_ref()],
-        bump = vault_state.state_bump,
-        close = user,
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub fn configure_withdrawal_queue(
+        ctx: Context<ConfigureWithdrawalQueue>,
+        delay: i64,
+        guardian: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.configure_withdrawal_queue(delay, guardian)
+    }
 
-impl<'info> Close<'info> {
-    pub fn close(&mut self) -> Result<()> {
+    pub fn request_withdrawal(
+        ctx: Context<RequestWithdrawal>,
+        amount: u64,
+        recipient: Pubkey,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.request_withdrawal(amount, recipient, mint, &ctx.bumps)
+    }
 
-        let system_program = 
-            self.system_program.to_account_info();
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        ctx.accounts.execute_withdrawal()
+    }
 
-        let cpi_accounts = Transfer {
-            from: self.vault.to_account_info(),
-            to: self.user.to_account_info(),
-        };
+    pub fn execute_token_withdrawal(ctx: Context<ExecuteTokenWithdrawal>) -> Result<()> {
+        ctx.accounts.execute_token_withdrawal()
+    }
 
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            b"vault",
-            self.vault_state.to_account_info().key.as_ref(),
-            &[self.vault_state.vault_bump],
-        ]];
-        
-        let cpi_ctx = 
-            CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        ctx.accounts.cancel_withdrawal()
+    }
 
-        transfer(cpi_ctx, self.vault.lamports())
+    pub fn deposit_for(ctx: Context<DepositFor>, amount: u64, memo: String) -> Result<()> {
+        ctx.accounts.deposit_for(amount, memo)
     }
 }
-
-#[account]
-#[derive(InitSpace)]
-pub struct VaultState {
-    pub vault_bump: u8,
-    pub state_bump: u8,
-}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VaultError;
+
+// A year is already far longer than any spending limit needs to be
+pub const MAX_ALLOWANCE_PERIOD: i64 = 365 * 24 * 60 * 60;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub vault_state: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,            // Lamports the delegate may withdraw per period
+    pub period: i64,            // Length of a period in seconds
+    pub period_start: i64,      // Start of the current period
+    pub spent: u64,             // Lamports withdrawn in the current period
+    pub bump: u8,
+}
+
+impl Allowance {
+    pub fn spend(&mut self, amount: u64, now: i64) -> Result<()> {
+
+        // Move the window forward by whole periods so it stays aligned to the grant
+        let period_end = self.period_start
+            .checked_add(self.period)
+            .ok_or(VaultError::Overflow)?;
+
+        if now >= period_end {
+            let elapsed_periods = (now - self.period_start) / self.period;
+            self.period_start += elapsed_periods * self.period;
+            self.spent = 0;
+        }
+
+        let spent = self.spent
+            .checked_add(amount)
+            .ok_or(VaultError::AllowanceExceeded)?;
+
+        require!(spent <= self.amount, VaultError::AllowanceExceeded);
+
+        self.spent = spent;
+
+        Ok(())
+    }
+}
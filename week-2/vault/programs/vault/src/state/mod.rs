@@ -0,0 +1,20 @@
+pub mod vault_state;
+pub use vault_state::*;
+
+pub mod multisig_state;
+pub use multisig_state::*;
+
+pub mod proposal;
+pub use proposal::*;
+
+pub mod allowance;
+pub use allowance::*;
+
+pub mod stream;
+pub use stream::*;
+
+pub mod vault_registry;
+pub use vault_registry::*;
+
+pub mod withdrawal_request;
+pub use withdrawal_request::*;
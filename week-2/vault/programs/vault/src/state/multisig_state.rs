@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VaultError;
+
+pub const MAX_OWNERS: usize = 10;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MultisigState {
+    pub creator: Pubkey,
+    #[max_len(MAX_OWNERS)]
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,          // Approvals needed before a proposal can be executed
+    pub proposal_count: u64,    // Used as the seed for the next proposal
+    pub vault_bump: u8,
+    pub state_bump: u8,
+}
+
+impl MultisigState {
+    pub fn owner_index(&self, owner: &Pubkey) -> Result<usize> {
+        self.owners
+            .iter()
+            .position(|o| o == owner)
+            .ok_or(error!(VaultError::NotAnOwner))
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub approvals: u16,         // Bit i is set once owners[i] approves
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VaultError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    pub vault_state: Pubkey,
+    pub recipient: Pubkey,
+    pub rate: u64,              // Lamports vested per second
+    pub start: i64,
+    pub end: i64,
+    pub withdrawn: u64,         // Lamports already paid out to the recipient
+    pub bump: u8,
+}
+
+impl Stream {
+    pub fn vested(&self, now: i64) -> Result<u64> {
+        let elapsed = now.min(self.end).saturating_sub(self.start).max(0);
+
+        self.rate
+            .checked_mul(elapsed as u64)
+            .ok_or(error!(VaultError::Overflow))
+    }
+
+    pub fn withdrawable(&self, now: i64) -> Result<u64> {
+        Ok(self.vested(now)?.saturating_sub(self.withdrawn))
+    }
+}
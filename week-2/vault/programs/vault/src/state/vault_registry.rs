@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VaultError;
+
+pub const MAX_VAULTS: usize = 16;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultRegistry {
+    #[max_len(MAX_VAULTS)]
+    pub indices: Vec<u64>,      // Indices of the user's open vaults
+    pub bump: u8,
+}
+
+impl VaultRegistry {
+    pub fn register(&mut self, index: u64) -> Result<()> {
+        require!(self.indices.len() < MAX_VAULTS, VaultError::RegistryFull);
+
+        self.indices.push(index);
+
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, index: u64) {
+        self.indices.retain(|i| *i != index);
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VaultError;
+
+// Long enough for any cancellation window, short enough that a mistyped
+// delay can't lock the vault for good
+pub const MAX_WITHDRAWAL_DELAY: i64 = 30 * 24 * 60 * 60;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultState {
+    pub index: u64,             // Lets a user open more than one vault
+    pub vault_bump: u8,
+    pub state_bump: u8,
+    pub unlock_at: Option<i64>, // Funds can't leave the vault before this timestamp
+    pub beneficiary: Option<Pubkey>, // Can claim the vault once the owner goes inactive
+    pub inactivity_period: i64, // Seconds without owner activity before the beneficiary can claim
+    pub last_active: i64,       // Last time the owner used the vault
+    pub withdrawal_delay: Option<i64>, // If set, withdrawals are queued for this many seconds
+    pub guardian: Option<Pubkey>, // Can cancel queued withdrawals alongside the owner
+    pub request_count: u64,     // Used as the seed for the next withdrawal request
+    pub total_deposited: u64,   // Lamports deposited over the vault's lifetime, rent included
+    pub total_withdrawn: u64,   // Lamports withdrawn over the vault's lifetime
+    pub last_activity: i64,     // Last deposit or withdrawal by anyone, unlike last_active
+}
+
+impl VaultState {
+    pub fn check_unlocked(&self) -> Result<()> {
+        if let Some(unlock_at) = self.unlock_at {
+            require!(
+                Clock::get()?.unix_timestamp >= unlock_at,
+                VaultError::VaultLocked
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn check_instant_withdrawal(&self) -> Result<()> {
+        require!(
+            self.withdrawal_delay.is_none(),
+            VaultError::WithdrawalQueueActive
+        );
+
+        Ok(())
+    }
+
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_deposited = self.total_deposited
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        self.last_activity = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.total_withdrawn = self.total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        self.last_activity = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn touch(&mut self) -> Result<()> {
+        self.last_active = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
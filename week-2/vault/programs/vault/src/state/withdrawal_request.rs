@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequest {
+    pub vault_state: Pubkey,
+    pub index: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,   // Lamports if unset, otherwise tokens from the vault's ATA for this mint
+    pub executable_at: i64,     // End of the cancellation window
+    pub bump: u8,
+}
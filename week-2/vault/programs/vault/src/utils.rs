@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+    },
+    token_2022_extensions::transfer_fee::{
+        harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint,
+    },
+};
+
+// Moves transfer fees withheld in a Token-2022 account over to its mint,
+// closing the account fails while any are left in it
+pub fn harvest_withheld_fees<'info>(
+    account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    if *token_program.key != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let withheld = {
+        let data = account.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+
+        state
+            .get_extension::<TransferFeeAmount>()
+            .map(|fee_amount| u64::from(fee_amount.withheld_amount))
+            .unwrap_or(0)
+    };
+
+    if withheld == 0 {
+        return Ok(());
+    }
+
+    let harvest_accounts = HarvestWithheldTokensToMint {
+        token_program_id: token_program.clone(),
+        mint,
+    };
+
+    let harvest_ctx = CpiContext::new(token_program, harvest_accounts);
+
+    harvest_withheld_tokens_to_mint(harvest_ctx, vec![account])
+}
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Vault } from "../target/types/vault";
 import { expect } from "chai";
+import { getAssociatedTokenAddressSync, createAssociatedTokenAccountInstruction, createMint, mintTo, getAccount, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ExtensionType, getMintLen, createInitializeTransferFeeConfigInstruction, createInitializeMintInstruction, getMint, getTransferFeeConfig } from "@solana/spl-token";
 
 describe("vault", () => {
   const provider = anchor.AnchorProvider.env();
@@ -10,8 +11,15 @@ describe("vault", () => {
   const program = anchor.workspace.Vault as Program<Vault>;
   const user = provider.wallet.publicKey;
 
+  const index = new anchor.BN(0);
+
   const [vaultStatePda, stateBump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("state"), user.toBuffer()],
+    [Buffer.from("state"), user.toBuffer(), index.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
+  const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("registry"), user.toBuffer()],
     program.programId
   );
 
@@ -20,6 +28,10 @@ describe("vault", () => {
     program.programId
   );
 
+  let mint: anchor.web3.PublicKey;
+  let userAta: anchor.web3.PublicKey;
+  let vaultAta: anchor.web3.PublicKey;
+
   before(async () => {
     await provider.connection.requestAirdrop(user, 10 * anchor.web3.LAMPORTS_PER_SOL);
 
@@ -28,7 +40,7 @@ describe("vault", () => {
 
   it("Initialize vault", async () => {
     await program.methods
-      .initialize()
+      .initialize(index, null)
       .accounts({
         user: user
       })
@@ -37,6 +49,11 @@ describe("vault", () => {
     const vaultState = await program.account.vaultState.fetch(vaultStatePda);
     expect(vaultState.vaultBump).to.equal(vaultBump);
     expect(vaultState.stateBump).to.equal(stateBump);
+    expect(vaultState.unlockAt).to.be.null;
+    expect(vaultState.index.toNumber()).to.equal(0);
+
+    const registry = await program.account.vaultRegistry.fetch(registryPda);
+    expect(registry.indices.map((i) => i.toNumber())).to.deep.equal([0]);
 
     const vaultBalance = await provider.connection.getBalance(vaultPda);
     const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
@@ -51,8 +68,9 @@ describe("vault", () => {
 
     await program.methods
       .deposit(new anchor.BN(depositAmount))
-      .accounts({
+      .accountsPartial({
         user: user,
+        vaultState: vaultStatePda,
       })
       .rpc();
 
@@ -60,6 +78,10 @@ describe("vault", () => {
     const finalUserBalance = await provider.connection.getBalance(user);
 
     expect(finalVaultBalance).to.equal(initialVaultBalance + depositAmount);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+    expect(vaultState.totalDeposited.toNumber()).to.equal(rentExempt + depositAmount);
     // User balance decreases by amount - fees
     expect(finalUserBalance).to.equal(initialUserBalance - depositAmount - 5000);
   });
@@ -72,8 +94,9 @@ describe("vault", () => {
 
     await program.methods
       .withdraw(new anchor.BN(withdrawAmount))
-      .accounts({
+      .accountsPartial({
         user: user,
+        vaultState: vaultStatePda,
       })
       .rpc();
 
@@ -81,10 +104,362 @@ describe("vault", () => {
     const finalUserBalance = await provider.connection.getBalance(user);
 
     expect(finalVaultBalance).to.equal(initialVaultBalance - withdrawAmount);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.totalWithdrawn.toNumber()).to.equal(withdrawAmount);
     // User balance increases by amount - fees
     expect(finalUserBalance).to.equal(initialUserBalance + withdrawAmount - 5000);
   });
 
+  it("Extend lock", async () => {
+    // A timestamp in the past keeps the vault usable for the remaining tests
+    const unlockAt = Math.floor(Date.now() / 1000) - 60;
+
+    await program.methods
+      .extendLock(new anchor.BN(unlockAt))
+      .accountsPartial({
+        user: user,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.unlockAt.toNumber()).to.equal(unlockAt);
+
+    try {
+      await program.methods
+        .extendLock(new anchor.BN(unlockAt - 1))
+        .accountsPartial({
+          user: user,
+          vaultState: vaultStatePda,
+        })
+        .rpc();
+      expect.fail("Shortening the lock should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("LockCannotBeShortened");
+    }
+  });
+
+  it("Deposit tokens into vault", async () => {
+    const depositAmount = 100;
+
+    mint = await createMint(provider.connection, provider.wallet.payer, user, null, 0);
+
+    userAta = getAssociatedTokenAddressSync(mint, user);
+    const userAtaTx = new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(user, userAta, user, mint)
+    );
+    await provider.sendAndConfirm(userAtaTx);
+    await mintTo(provider.connection, provider.wallet.payer, mint, userAta, provider.wallet.payer, depositAmount);
+
+    vaultAta = getAssociatedTokenAddressSync(mint, vaultStatePda, true);
+
+    await program.methods
+      .depositToken(new anchor.BN(depositAmount))
+      .accountsPartial({
+        user: user,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    expect(Number((await getAccount(provider.connection, vaultAta)).amount)).to.equal(depositAmount);
+    expect(Number((await getAccount(provider.connection, userAta)).amount)).to.equal(0);
+  });
+
+  it("Withdraw tokens from vault", async () => {
+    await program.methods
+      .withdrawToken(new anchor.BN(40))
+      .accountsPartial({
+        user: user,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    expect(Number((await getAccount(provider.connection, vaultAta)).amount)).to.equal(60);
+    expect(Number((await getAccount(provider.connection, userAta)).amount)).to.equal(40);
+  });
+
+  it("Close token vault", async () => {
+    await program.methods
+      .closeTokenVault()
+      .accountsPartial({
+        user: user,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(vaultAta)).to.be.null;
+    expect(Number((await getAccount(provider.connection, userAta)).amount)).to.equal(100);
+  });
+
+  it("Closes a Token-2022 vault that holds withheld transfer fees", async () => {
+    // 1% on every transfer, withheld in the receiving account
+    const feeMint = anchor.web3.Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: user,
+        newAccountPubkey: feeMint.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(feeMint.publicKey, user, user, 100, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID),
+      createInitializeMintInstruction(feeMint.publicKey, 0, user, null, TOKEN_2022_PROGRAM_ID)
+    ), [feeMint]);
+
+    const feeUserAta = getAssociatedTokenAddressSync(feeMint.publicKey, user, false, TOKEN_2022_PROGRAM_ID);
+    const feeVaultAta = getAssociatedTokenAddressSync(feeMint.publicKey, vaultStatePda, true, TOKEN_2022_PROGRAM_ID);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(user, feeUserAta, user, feeMint.publicKey, TOKEN_2022_PROGRAM_ID)
+    ));
+    await mintTo(provider.connection, provider.wallet.payer, feeMint.publicKey, feeUserAta, provider.wallet.payer, 1000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    const tokenAccounts = {
+      user: user,
+      mint: feeMint.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      vaultState: vaultStatePda,
+    };
+
+    await program.methods
+      .depositToken(new anchor.BN(1000))
+      .accountsPartial(tokenAccounts)
+      .rpc();
+
+    expect(Number((await getAccount(provider.connection, feeVaultAta, undefined, TOKEN_2022_PROGRAM_ID)).amount)).to.equal(990);
+
+    await program.methods
+      .closeTokenVault()
+      .accountsPartial(tokenAccounts)
+      .rpc();
+
+    // The fee withheld in the vault moved to the mint so the vault could close
+    expect(await provider.connection.getAccountInfo(feeVaultAta)).to.be.null;
+    const feeConfig = getTransferFeeConfig(await getMint(provider.connection, feeMint.publicKey, undefined, TOKEN_2022_PROGRAM_ID));
+    expect(Number(feeConfig.withheldAmount)).to.equal(10);
+  });
+
+  it("Delegate withdraws within its allowance", async () => {
+    const delegate = anchor.web3.Keypair.generate();
+    const allowanceAmount = 0.2 * anchor.web3.LAMPORTS_PER_SOL;
+
+    const [allowancePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allowance"), vaultStatePda.toBuffer(), delegate.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .grantAllowance(delegate.publicKey, new anchor.BN(allowanceAmount), new anchor.BN(366 * 86400))
+        .accountsPartial({
+          user: user,
+          vaultState: vaultStatePda,
+        })
+        .rpc();
+      expect.fail("Periods longer than a year should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidPeriod");
+    }
+
+    const lastActive = (await program.account.vaultState.fetch(vaultStatePda)).lastActive.toNumber();
+
+    await program.methods
+      .grantAllowance(delegate.publicKey, new anchor.BN(allowanceAmount), new anchor.BN(86400))
+      .accountsPartial({
+        user: user,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    // Granting an allowance counts as owner activity
+    expect((await program.account.vaultState.fetch(vaultStatePda)).lastActive.toNumber()).to.be.at.least(lastActive);
+
+    await program.methods
+      .delegateWithdraw(new anchor.BN(allowanceAmount))
+      .accountsPartial({
+        delegate: delegate.publicKey,
+        owner: user,
+        vaultState: vaultStatePda,
+      })
+      .signers([delegate])
+      .rpc();
+
+    expect(await provider.connection.getBalance(delegate.publicKey)).to.equal(allowanceAmount);
+
+    try {
+      await program.methods
+        .delegateWithdraw(new anchor.BN(1))
+        .accountsPartial({
+          delegate: delegate.publicKey,
+          owner: user,
+          vaultState: vaultStatePda,
+        })
+        .signers([delegate])
+        .rpc();
+      expect.fail("Withdrawing past the allowance should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AllowanceExceeded");
+    }
+
+    await program.methods
+      .revokeAllowance()
+      .accountsPartial({
+        user: user,
+        allowance: allowancePda,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(allowancePda)).to.be.null;
+  });
+
+  it("Beneficiary cannot claim while the owner is active", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .setBeneficiary(beneficiary.publicKey, new anchor.BN(86400))
+      .accountsPartial({
+        user: user,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    await program.methods
+      .heartbeat()
+      .accountsPartial({
+        user: user,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.beneficiary.toBase58()).to.equal(beneficiary.publicKey.toBase58());
+    expect(vaultState.lastActive.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods
+        .claimInheritance()
+        .accountsPartial({
+          beneficiary: beneficiary.publicKey,
+          owner: user,
+          vaultState: vaultStatePda,
+        })
+        .signers([beneficiary])
+        .rpc();
+      expect.fail("Claiming while the owner is active should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OwnerStillActive");
+    }
+  });
+
+  it("Recipient withdraws the vested part of a stream", async () => {
+    const recipient = anchor.web3.Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const rate = 1_000_000;
+
+    const [streamPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("stream"), vaultStatePda.toBuffer(), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createStream(recipient.publicKey, new anchor.BN(rate), new anchor.BN(now - 10), new anchor.BN(now + 100))
+      .accountsPartial({
+        user: user,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    await program.methods
+      .withdrawStreamed()
+      .accountsPartial({
+        recipient: recipient.publicKey,
+        owner: user,
+        vaultState: vaultStatePda,
+      })
+      .signers([recipient])
+      .rpc();
+
+    const stream = await program.account.stream.fetch(streamPda);
+    expect(stream.withdrawn.toNumber()).to.be.greaterThan(0);
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(stream.withdrawn.toNumber());
+
+    await program.methods
+      .cancelStream()
+      .accountsPartial({
+        user: user,
+        recipient: recipient.publicKey,
+        stream: streamPda,
+        vaultState: vaultStatePda,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(streamPda)).to.be.null;
+
+    try {
+      await program.methods
+        .createStream(recipient.publicKey, new anchor.BN("18446744073709551615"), new anchor.BN(now), new anchor.BN(now + 2))
+        .accountsPartial({
+          user: user,
+          vaultState: vaultStatePda,
+        })
+        .rpc();
+      expect.fail("A stream that can't vest in full should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Overflow");
+    }
+  });
+
+  it("Anyone can deposit into the vault with a memo", async () => {
+    const payer = anchor.web3.Keypair.generate();
+    const depositAmount = 0.1 * anchor.web3.LAMPORTS_PER_SOL;
+
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: user,
+        toPubkey: payer.publicKey,
+        lamports: 0.2 * anchor.web3.LAMPORTS_PER_SOL,
+      })
+    ));
+
+    const initialVaultBalance = await provider.connection.getBalance(vaultPda);
+
+    await program.methods
+      .depositFor(new anchor.BN(depositAmount), "invoice #42")
+      .accountsPartial({
+        payer: payer.publicKey,
+        owner: user,
+        vaultState: vaultStatePda,
+      })
+      .signers([payer])
+      .rpc();
+
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(initialVaultBalance + depositAmount);
+
+    try {
+      await program.methods
+        .depositFor(new anchor.BN(depositAmount), "x".repeat(65))
+        .accountsPartial({
+          payer: payer.publicKey,
+          owner: user,
+          vaultState: vaultStatePda,
+        })
+        .signers([payer])
+        .rpc();
+      expect.fail("Oversized memos should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MemoTooLong");
+    }
+  });
+
   it("Close vault", async () => {
     const initialVaultBalance = await provider.connection.getBalance(vaultPda);
     const initialVaultStateBalance = await provider.connection.getBalance(vaultStatePda);
@@ -92,8 +467,9 @@ describe("vault", () => {
 
     await program.methods
       .close()
-      .accounts({
+      .accountsPartial({
         user: user,
+        vaultState: vaultStatePda,
       })
       .rpc();
 
@@ -106,7 +482,534 @@ describe("vault", () => {
     const vaultStateInfo = await provider.connection.getAccountInfo(vaultStatePda);
     expect(vaultStateInfo).to.be.null;
 
+    const registry = await program.account.vaultRegistry.fetch(registryPda);
+    expect(registry.indices).to.be.empty;
+
     // User gets back the remaining balance - fees
     expect(finalUserBalance).to.equal(initialUserBalance + initialVaultBalance + initialVaultStateBalance - 5000);
   });
+
+  it("Emits events and totals that reconcile once the vault is closed", async () => {
+    const eventsIndex = new anchor.BN(4);
+    const depositAmount = anchor.web3.LAMPORTS_PER_SOL;
+    const withdrawAmount = 0.25 * anchor.web3.LAMPORTS_PER_SOL;
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+    const parser = new anchor.EventParser(program.programId, program.coder);
+
+    const [eventsStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.toBuffer(), eventsIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const eventsOf = async (signature: string) => {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...parser.parseLogs(tx.meta.logMessages)];
+    };
+
+    await program.methods
+      .initialize(eventsIndex, null)
+      .accounts({
+        user: user,
+      })
+      .rpc();
+
+    let vaultState = await program.account.vaultState.fetch(eventsStatePda);
+    expect(vaultState.totalDeposited.toNumber()).to.equal(rentExempt);
+    expect(vaultState.totalWithdrawn.toNumber()).to.equal(0);
+    expect(vaultState.lastActivity.toNumber()).to.equal(vaultState.lastActive.toNumber());
+
+    const depositSignature = await program.methods
+      .deposit(new anchor.BN(depositAmount))
+      .accountsPartial({
+        user: user,
+        vaultState: eventsStatePda,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const [depositEvent] = await eventsOf(depositSignature);
+    expect(depositEvent.name).to.equal("depositEvent");
+    expect(depositEvent.data.vaultState.equals(eventsStatePda)).to.be.true;
+    expect(depositEvent.data.amount.toNumber()).to.equal(depositAmount);
+    expect(depositEvent.data.totalDeposited.toNumber()).to.equal(rentExempt + depositAmount);
+
+    vaultState = await program.account.vaultState.fetch(eventsStatePda);
+    expect(vaultState.totalDeposited.toNumber()).to.equal(rentExempt + depositAmount);
+    expect(vaultState.lastActivity.toNumber()).to.be.greaterThan(0);
+
+    const withdrawSignature = await program.methods
+      .withdraw(new anchor.BN(withdrawAmount))
+      .accountsPartial({
+        user: user,
+        vaultState: eventsStatePda,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const [withdrawEvent] = await eventsOf(withdrawSignature);
+    expect(withdrawEvent.name).to.equal("withdrawEvent");
+    expect(withdrawEvent.data.amount.toNumber()).to.equal(withdrawAmount);
+    expect(withdrawEvent.data.totalWithdrawn.toNumber()).to.equal(withdrawAmount);
+
+    const lastActivity = (await program.account.vaultState.fetch(eventsStatePda)).lastActivity.toNumber();
+    expect(lastActivity).to.be.at.least(vaultState.lastActivity.toNumber());
+
+    const closeSignature = await program.methods
+      .close()
+      .accountsPartial({
+        user: user,
+        vaultState: eventsStatePda,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    // Everything that went in, rent included, has come back out
+    const [closeEvent] = await eventsOf(closeSignature);
+    expect(closeEvent.name).to.equal("closeEvent");
+    expect(closeEvent.data.amount.toNumber()).to.equal(rentExempt + depositAmount - withdrawAmount);
+    expect(closeEvent.data.totalDeposited.toNumber()).to.equal(rentExempt + depositAmount);
+    expect(closeEvent.data.totalWithdrawn.toNumber()).to.equal(closeEvent.data.totalDeposited.toNumber());
+  });
+
+  it("Queued withdrawals wait for the delay and can be cancelled by the guardian", async () => {
+    const queueIndex = new anchor.BN(1);
+    const guardian = anchor.web3.Keypair.generate();
+    const recipient = anchor.web3.Keypair.generate();
+
+    const [queueStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.toBuffer(), queueIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const [requestPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("request"), queueStatePda.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(queueIndex, null)
+      .accounts({
+        user: user,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .accountsPartial({
+        user: user,
+        vaultState: queueStatePda,
+      })
+      .rpc();
+
+    await program.methods
+      .configureWithdrawalQueue(new anchor.BN(3600), guardian.publicKey)
+      .accountsPartial({
+        user: user,
+        guardian: null,
+        vaultState: queueStatePda,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(1))
+        .accountsPartial({
+          user: user,
+          vaultState: queueStatePda,
+        })
+        .rpc();
+      expect.fail("Instant withdrawals should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("WithdrawalQueueActive");
+    }
+
+    await program.methods
+      .requestWithdrawal(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL), recipient.publicKey, null)
+      .accountsPartial({
+        user: user,
+        vaultState: queueStatePda,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .executeWithdrawal()
+        .accountsPartial({
+          user: user,
+          recipient: recipient.publicKey,
+          vaultState: queueStatePda,
+          request: requestPda,
+        })
+        .rpc();
+      expect.fail("Executing before the delay should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("WithdrawalNotReady");
+    }
+
+    await program.methods
+      .cancelWithdrawal()
+      .accountsPartial({
+        authority: guardian.publicKey,
+        owner: user,
+        vaultState: queueStatePda,
+        request: requestPda,
+      })
+      .signers([guardian])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(requestPda)).to.be.null;
+
+    const registry = await program.account.vaultRegistry.fetch(registryPda);
+    expect(registry.indices.map((i) => i.toNumber())).to.deep.equal([1]);
+  });
+
+  it("Owner-only outflows can't bypass the withdrawal queue", async () => {
+    const queueIndex = new anchor.BN(1);
+    const thief = anchor.web3.Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+
+    const [queueStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.toBuffer(), queueIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .depositToken(new anchor.BN(10))
+      .accountsPartial({
+        user: user,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        vaultState: queueStatePda,
+      })
+      .rpc();
+
+    const bypasses = [
+      program.methods
+        .createStream(thief.publicKey, new anchor.BN(anchor.web3.LAMPORTS_PER_SOL), new anchor.BN(now - 3600), new anchor.BN(now + 3600))
+        .accountsPartial({ user: user, vaultState: queueStatePda }),
+      program.methods
+        .grantAllowance(thief.publicKey, new anchor.BN(anchor.web3.LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial({ user: user, vaultState: queueStatePda }),
+      program.methods
+        .setBeneficiary(thief.publicKey, new anchor.BN(1))
+        .accountsPartial({ user: user, vaultState: queueStatePda }),
+      program.methods
+        .withdrawToken(new anchor.BN(10))
+        .accountsPartial({ user: user, mint: mint, tokenProgram: TOKEN_PROGRAM_ID, vaultState: queueStatePda }),
+      program.methods
+        .closeTokenVault()
+        .accountsPartial({ user: user, mint: mint, tokenProgram: TOKEN_PROGRAM_ID, vaultState: queueStatePda }),
+    ];
+
+    for (const bypass of bypasses) {
+      try {
+        await bypass.rpc();
+        expect.fail("Instant outflows should be rejected while the queue is on");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("WithdrawalQueueActive");
+      }
+    }
+
+    const queueVaultAta = getAssociatedTokenAddressSync(mint, queueStatePda, true);
+    expect(Number((await getAccount(provider.connection, queueVaultAta)).amount)).to.equal(10);
+  });
+
+  it("Queues token withdrawals and keeps the guardian in place", async () => {
+    const tokenQueueIndex = new anchor.BN(3);
+    const guardian = anchor.web3.Keypair.generate();
+    const recipient = anchor.web3.Keypair.generate();
+
+    const [tokenQueueStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.toBuffer(), tokenQueueIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [requestPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("request"), tokenQueueStatePda.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(tokenQueueIndex, null)
+      .accounts({
+        user: user,
+      })
+      .rpc();
+
+    await program.methods
+      .configureWithdrawalQueue(new anchor.BN(1), guardian.publicKey)
+      .accountsPartial({
+        user: user,
+        guardian: null,
+        vaultState: tokenQueueStatePda,
+      })
+      .rpc();
+
+    // A delay past the maximum would lock the vault for good
+    try {
+      await program.methods
+        .configureWithdrawalQueue(new anchor.BN(31 * 24 * 60 * 60), guardian.publicKey)
+        .accountsPartial({ user: user, guardian: null, vaultState: tokenQueueStatePda })
+        .rpc();
+      expect.fail("Delays past the maximum should be rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidWithdrawalDelay");
+    }
+
+    // The owner key alone can't drop the guardian
+    try {
+      await program.methods
+        .configureWithdrawalQueue(new anchor.BN(1), null)
+        .accountsPartial({ user: user, guardian: null, vaultState: tokenQueueStatePda })
+        .rpc();
+      expect.fail("Removing the guardian should need its signature");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("GuardianSignatureRequired");
+    }
+
+    const queueMint = await createMint(provider.connection, provider.wallet.payer, user, null, 0);
+    const ownerAta = getAssociatedTokenAddressSync(queueMint, user);
+    const recipientAta = getAssociatedTokenAddressSync(queueMint, recipient.publicKey);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(user, ownerAta, user, queueMint)
+    ));
+    await mintTo(provider.connection, provider.wallet.payer, queueMint, ownerAta, provider.wallet.payer, 25);
+
+    await program.methods
+      .depositToken(new anchor.BN(25))
+      .accountsPartial({
+        user: user,
+        mint: queueMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        vaultState: tokenQueueStatePda,
+      })
+      .rpc();
+
+    await program.methods
+      .requestWithdrawal(new anchor.BN(25), recipient.publicKey, queueMint)
+      .accountsPartial({
+        user: user,
+        vaultState: tokenQueueStatePda,
+      })
+      .rpc();
+
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    // A token request can't be paid out in lamports
+    try {
+      await program.methods
+        .executeWithdrawal()
+        .accountsPartial({
+          user: user,
+          recipient: recipient.publicKey,
+          vaultState: tokenQueueStatePda,
+          request: requestPda,
+        })
+        .rpc();
+      expect.fail("Token requests should only execute as token withdrawals");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidWithdrawalRequest");
+    }
+
+    await program.methods
+      .executeTokenWithdrawal()
+      .accountsPartial({
+        user: user,
+        recipient: recipient.publicKey,
+        vaultState: tokenQueueStatePda,
+        mint: queueMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        request: requestPda,
+      })
+      .rpc();
+
+    expect(Number((await getAccount(provider.connection, recipientAta)).amount)).to.equal(25);
+    expect(await provider.connection.getAccountInfo(requestPda)).to.be.null;
+
+    // Once emptied through the queue the token vault can be closed
+    await program.methods
+      .closeTokenVault()
+      .accountsPartial({ user: user, mint: queueMint, tokenProgram: TOKEN_PROGRAM_ID, vaultState: tokenQueueStatePda })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(getAssociatedTokenAddressSync(queueMint, tokenQueueStatePda, true))).to.be.null;
+  });
+
+  it("Beneficiary sweeps the vault and its token vaults once the owner goes inactive", async () => {
+    const claimIndex = new anchor.BN(2);
+    const beneficiary = anchor.web3.Keypair.generate();
+
+    const [claimStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.toBuffer(), claimIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(claimIndex, null)
+      .accounts({
+        user: user,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 2))
+      .accountsPartial({
+        user: user,
+        vaultState: claimStatePda,
+      })
+      .rpc();
+
+    const claimMint = await createMint(provider.connection, provider.wallet.payer, user, null, 0);
+    const ownerAta = getAssociatedTokenAddressSync(claimMint, user);
+    const claimVaultAta = getAssociatedTokenAddressSync(claimMint, claimStatePda, true);
+    const beneficiaryAta = getAssociatedTokenAddressSync(claimMint, beneficiary.publicKey);
+
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(user, ownerAta, user, claimMint),
+      createAssociatedTokenAccountInstruction(user, beneficiaryAta, beneficiary.publicKey, claimMint)
+    ));
+    await mintTo(provider.connection, provider.wallet.payer, claimMint, ownerAta, provider.wallet.payer, 50);
+
+    await program.methods
+      .depositToken(new anchor.BN(50))
+      .accountsPartial({
+        user: user,
+        mint: claimMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        vaultState: claimStatePda,
+      })
+      .rpc();
+
+    await program.methods
+      .setBeneficiary(beneficiary.publicKey, new anchor.BN(1))
+      .accountsPartial({
+        user: user,
+        vaultState: claimStatePda,
+      })
+      .rpc();
+
+    await new Promise(resolve => setTimeout(resolve, 3000));
+
+    await program.methods
+      .claimInheritance()
+      .accountsPartial({
+        beneficiary: beneficiary.publicKey,
+        owner: user,
+        vaultState: claimStatePda,
+      })
+      .remainingAccounts([
+        { pubkey: claimMint, isSigner: false, isWritable: true },
+        { pubkey: claimVaultAta, isSigner: false, isWritable: true },
+        { pubkey: beneficiaryAta, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ])
+      .signers([beneficiary])
+      .rpc();
+
+    expect(Number((await getAccount(provider.connection, beneficiaryAta)).amount)).to.equal(50);
+    expect(await provider.connection.getAccountInfo(claimVaultAta)).to.be.null;
+    expect(await provider.connection.getAccountInfo(claimStatePda)).to.be.null;
+    expect(await provider.connection.getBalance(beneficiary.publicKey)).to.be.greaterThan(anchor.web3.LAMPORTS_PER_SOL / 2);
+  });
+})
+
+describe("vault multisig", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vault as Program<Vault>;
+  const creator = provider.wallet.publicKey;
+  const ownerB = anchor.web3.Keypair.generate();
+  const ownerC = anchor.web3.Keypair.generate();
+  const recipient = anchor.web3.Keypair.generate();
+
+  const [multisigStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig"), creator.toBuffer()],
+    program.programId
+  );
+
+  const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), multisigStatePda.toBuffer()],
+    program.programId
+  );
+
+  const [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("proposal"), multisigStatePda.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
+  const amount = 0.25 * anchor.web3.LAMPORTS_PER_SOL;
+
+  it("Initialize and fund a 2-of-3 multisig vault", async () => {
+    await program.methods
+      .initializeMultisig([creator, ownerB.publicKey, ownerC.publicKey], 2)
+      .accounts({
+        creator: creator,
+      })
+      .rpc();
+
+    await program.methods
+      .depositMultisig(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .accounts({
+        user: creator,
+        multisigState: multisigStatePda,
+      })
+      .rpc();
+
+    const multisigState = await program.account.multisigState.fetch(multisigStatePda);
+    expect(multisigState.owners.length).to.equal(3);
+    expect(multisigState.threshold).to.equal(2);
+  });
+
+  it("Executes a proposal only after the threshold is reached", async () => {
+    await program.methods
+      .proposeWithdrawal(new anchor.BN(amount), recipient.publicKey)
+      .accounts({
+        proposer: creator,
+        multisigState: multisigStatePda,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .executeProposal()
+        .accountsPartial({
+          owner: creator,
+          multisigState: multisigStatePda,
+          proposal: proposalPda,
+          proposer: creator,
+          recipient: recipient.publicKey,
+        })
+        .rpc();
+      expect.fail("Execution below the threshold should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ThresholdNotReached");
+    }
+
+    await program.methods
+      .approveProposal()
+      .accountsPartial({
+        owner: ownerB.publicKey,
+        multisigState: multisigStatePda,
+        proposal: proposalPda,
+      })
+      .signers([ownerB])
+      .rpc();
+
+    const initialVaultBalance = await provider.connection.getBalance(vaultPda);
+
+    await program.methods
+      .executeProposal()
+      .accountsPartial({
+        owner: creator,
+        multisigState: multisigStatePda,
+        proposal: proposalPda,
+        proposer: creator,
+        recipient: recipient.publicKey,
+      })
+      .rpc();
+
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(amount);
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(initialVaultBalance - amount);
+    expect(await provider.connection.getAccountInfo(proposalPda)).to.be.null;
+  });
 })
\ No newline at end of file
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AmmError, state::Config};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+        constraint = config.pending_authority == Some(new_authority.key()) @ AmmError::InvalidAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> AcceptAuthority<'info> {
+    pub fn accept_authority(&mut self) -> Result<()> {
+        self.config.authority = self.config.pending_authority.take();
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::state::Config;
+use crate::{error::AmmError, state::Config};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -57,9 +57,12 @@ impl<'info> Initialize<'info> {
         authority: Option<Pubkey>,
         bumps: InitializeBumps,
     ) -> Result<()> {
+        require!(fee <= 10000, AmmError::InvalidFee);
+
         self.config.set_inner(Config {
             seed,
             authority,
+            pending_authority: None,
             mint_x: self.mint_x.key(),
             mint_y: self.mint_y.key(),
             fee,
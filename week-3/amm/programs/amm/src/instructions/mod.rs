@@ -1,9 +1,13 @@
+pub mod accept_authority;
 pub mod deposit;
 pub mod initialize;
 pub mod swap;
+pub mod update_config;
 pub mod withdraw;
 
+pub use accept_authority::*;
 pub use deposit::*;
 pub use initialize::*;
 pub use swap::*;
+pub use update_config::*;
 pub use withdraw::*;
\ No newline at end of file
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AmmError, state::Config};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn lock(&mut self) -> Result<()> {
+        self.check_authority()?;
+
+        self.config.locked = true;
+
+        Ok(())
+    }
+
+    pub fn unlock(&mut self) -> Result<()> {
+        self.check_authority()?;
+
+        self.config.locked = false;
+
+        Ok(())
+    }
+
+    pub fn update_fee(&mut self, fee: u16) -> Result<()> {
+        self.check_authority()?;
+        require!(fee <= 10000, AmmError::InvalidFee);
+
+        self.config.fee = fee;
+
+        Ok(())
+    }
+
+    pub fn transfer_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        self.check_authority()?;
+
+        // The current authority stays in charge until the new one accepts
+        self.config.pending_authority = Some(new_authority);
+
+        Ok(())
+    }
+
+    fn check_authority(&self) -> Result<()> {
+        let authority = self.config.authority.ok_or(AmmError::NoAuthoritySet)?;
+        require_keys_eq!(authority, self.authority.key(), AmmError::InvalidAuthority);
+
+        Ok(())
+    }
+}
@@ -31,4 +31,24 @@ pub mod amm {
     pub fn swap(ctx: Context<Swap>, is_x: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
         ctx.accounts.swap(is_x, amount_in, min_amount_out)
     }
+
+    pub fn lock_pool(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.lock()
+    }
+
+    pub fn unlock_pool(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.unlock()
+    }
+
+    pub fn update_fee(ctx: Context<UpdateConfig>, fee: u16) -> Result<()> {
+        ctx.accounts.update_fee(fee)
+    }
+
+    pub fn transfer_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.transfer_authority(new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.accept_authority()
+    }
 }
\ No newline at end of file
@@ -392,4 +392,126 @@ describe("amm", () => {
       assert.equal(err.error.errorCode.code, "SlippageExceeded");    
     }
   });
+  it("Locks the pool and rejects deposits", async () => {
+    await program.methods
+      .lockPool()
+      .accountsPartial({ authority: wallet.publicKey, config })
+      .rpc();
+
+    assert.equal((await program.account.config.fetch(config)).locked, true);
+
+    try {
+      await program.methods
+        .deposit(new anchor.BN(1_000_000), new anchor.BN(10_000_000), new anchor.BN(10_000_000))
+        .accountsPartial({
+          user: wallet.publicKey,
+          mintX,
+          mintY,
+          config,
+          mintLp: mintLP,
+          vaultX,
+          vaultY,
+          userX,
+          userY,
+          userLp
+        })
+        .rpc();
+      throw new Error("Deposit did not fail");
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal(err.error.errorCode.code, "PoolLocked");
+    }
+  });
+
+  it("Unlocks the pool", async () => {
+    await program.methods
+      .unlockPool()
+      .accountsPartial({ authority: wallet.publicKey, config })
+      .rpc();
+
+    assert.equal((await program.account.config.fetch(config)).locked, false);
+  });
+
+  it("Updates the fee", async () => {
+    await program.methods
+      .updateFee(50)
+      .accountsPartial({ authority: wallet.publicKey, config })
+      .rpc();
+
+    assert.equal((await program.account.config.fetch(config)).fee, 50);
+  });
+
+  it("Update fee fails above 100%", async () => {
+    try {
+      await program.methods
+        .updateFee(10_001)
+        .accountsPartial({ authority: wallet.publicKey, config })
+        .rpc();
+      throw new Error("Update fee did not fail");
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal(err.error.errorCode.code, "InvalidFee");
+    }
+  });
+
+  it("Lock fails for anyone but the authority", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .lockPool()
+        .accountsPartial({ authority: stranger.publicKey, config })
+        .signers([stranger])
+        .rpc();
+      throw new Error("Lock did not fail");
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal(err.error.errorCode.code, "InvalidAuthority");
+    }
+  });
+
+  it("Transfers the authority in two steps", async () => {
+    const newAuthority = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .transferAuthority(newAuthority.publicKey)
+      .accountsPartial({ authority: wallet.publicKey, config })
+      .rpc();
+
+    let configAccount = await program.account.config.fetch(config);
+    assert.equal(configAccount.authority.toBase58(), wallet.publicKey.toBase58());
+    assert.equal(configAccount.pendingAuthority.toBase58(), newAuthority.publicKey.toBase58());
+
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accountsPartial({ newAuthority: wallet.publicKey, config })
+        .rpc();
+      throw new Error("Accept authority did not fail");
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal(err.error.errorCode.code, "InvalidAuthority");
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accountsPartial({ newAuthority: newAuthority.publicKey, config })
+      .signers([newAuthority])
+      .rpc();
+
+    configAccount = await program.account.config.fetch(config);
+    assert.equal(configAccount.authority.toBase58(), newAuthority.publicKey.toBase58());
+    assert.equal(configAccount.pendingAuthority, null);
+
+    try {
+      await program.methods
+        .unlockPool()
+        .accountsPartial({ authority: wallet.publicKey, config })
+        .rpc();
+      throw new Error("Unlock did not fail");
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal(err.error.errorCode.code, "InvalidAuthority");
+    }
+  });
 });